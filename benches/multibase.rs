@@ -1,75 +1,101 @@
 use std::hint::black_box;
 
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use rand::RngExt;
 
-use multibase::{decode, encode, Base};
+use multibase::{decode, encode, transcode, Base};
+
+/// Input sizes from 32 B to 1 MiB.
+const SIZES: [usize; 6] = [32, 256, 1 << 10, 1 << 14, 1 << 16, 1 << 20];
+
+const BASES: [Base; 4] = [
+    Base::Base32Upper,
+    Base::Base36Lower,
+    Base::Base58Btc,
+    Base::Base64,
+];
 
 fn bench_encode(c: &mut Criterion) {
     let mut rng = rand::rng();
-    let data: Vec<u8> = (0..1024).map(|_| rng.random()).collect();
 
     let mut group = c.benchmark_group("encode");
-    group.bench_function("base32", |b| {
-        b.iter(|| {
-            let _ = black_box(encode(Base::Base32Upper, &data));
-        })
-    });
-    group.bench_function("base58btc", |b| {
-        b.iter(|| {
-            let _ = black_box(encode(Base::Base58Btc, &data));
-        })
-    });
-    group.bench_function("base64", |b| {
-        b.iter(|| {
-            let _ = black_box(encode(Base::Base64, &data));
-        })
-    });
+    for size in SIZES {
+        let data: Vec<u8> = (0..size).map(|_| rng.random()).collect();
+        group.throughput(Throughput::Bytes(size as u64));
+        if size >= 1 << 16 {
+            group.sample_size(10);
+        }
+        for base in BASES {
+            group.bench_with_input(BenchmarkId::new(base.name(), size), &data, |b, data| {
+                b.iter(|| {
+                    let _ = black_box(encode(base, data));
+                })
+            });
+        }
+    }
     group.finish();
 }
 
 fn bench_decode(c: &mut Criterion) {
     let mut rng = rand::rng();
-    let data: Vec<usize> = (0..1024).map(|_| rng.random::<u32>() as usize).collect();
 
-    let base32 = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
-    let base58 = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
-    let base64 = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut group = c.benchmark_group("decode");
+    for size in SIZES {
+        let data: Vec<u8> = (0..size).map(|_| rng.random()).collect();
+        group.throughput(Throughput::Bytes(size as u64));
+        if size >= 1 << 16 {
+            group.sample_size(10);
+        }
+        for base in BASES {
+            let encoded = encode(base, &data);
+            group.bench_with_input(
+                BenchmarkId::new(base.name(), size),
+                &encoded,
+                |b, encoded| {
+                    b.iter(|| {
+                        let _ = black_box(decode(encoded).unwrap());
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
 
-    let mut base32_data = data
-        .iter()
-        .map(|i| base32[i % 31] as char)
-        .collect::<String>();
-    base32_data.insert(0, Base::Base32Upper.code());
-    let mut base58_data = data
-        .iter()
-        .map(|i| base58[i % 57] as char)
-        .collect::<String>();
-    base58_data.insert(0, Base::Base58Btc.code());
-    let mut base64_data = data
-        .iter()
-        .map(|i| base64[i % 64] as char)
-        .collect::<String>();
-    base64_data.insert(0, Base::Base64.code());
+fn bench_transcode(c: &mut Criterion) {
+    let mut rng = rand::rng();
 
-    let mut group = c.benchmark_group("decode");
-    group.bench_function("base32", |b| {
-        b.iter(|| {
-            let _ = black_box(decode(&base32_data).unwrap());
-        })
-    });
-    group.bench_function("base58btc", |b| {
-        b.iter(|| {
-            let _ = black_box(decode(&base58_data).unwrap());
-        })
-    });
-    group.bench_function("base64", |b| {
-        b.iter(|| {
-            let _ = black_box(decode(&base64_data).unwrap());
-        })
-    });
+    // Symbol by symbol from Base32Upper to Base32Lower, against decoding and encoding again.
+    let mut group = c.benchmark_group("transcode");
+    for size in SIZES {
+        let data: Vec<u8> = (0..size).map(|_| rng.random()).collect();
+        group.throughput(Throughput::Bytes(size as u64));
+        if size >= 1 << 16 {
+            group.sample_size(10);
+        }
+        let encoded = encode(Base::Base32Upper, &data);
+        group.bench_with_input(
+            BenchmarkId::new("transcode", size),
+            &encoded,
+            |b, encoded| {
+                b.iter(|| {
+                    let _ = black_box(transcode(encoded, Base::Base32Lower).unwrap());
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("decode_encode", size),
+            &encoded,
+            |b, encoded| {
+                b.iter(|| {
+                    let (_, decoded) = decode(encoded).unwrap();
+                    let _ = black_box(encode(Base::Base32Lower, decoded));
+                })
+            },
+        );
+    }
     group.finish();
 }
 
-criterion_group!(benches, bench_encode, bench_decode);
+criterion_group!(benches, bench_encode, bench_decode, bench_transcode);
 criterion_main!(benches);
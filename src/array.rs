@@ -0,0 +1,188 @@
+//! Fixed-capacity outputs, for targets without an allocator.
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::str::Utf8Error;
+
+/// A string of at most `N` bytes stored inline, see [`encode_to_array`](crate::encode_to_array).
+#[derive(Clone, Copy)]
+pub struct ArrayString<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> ArrayString<N> {
+    /// Take the first `len` bytes of `bytes`, which must be valid UTF-8.
+    pub(crate) fn from_utf8(bytes: [u8; N], len: usize) -> Result<Self, Utf8Error> {
+        core::str::from_utf8(&bytes[..len])?;
+        Ok(Self { bytes, len })
+    }
+
+    /// Take the longest prefix of `s` of at most `N` bytes ending at a char boundary.
+    pub(crate) fn truncated(s: &str) -> Self {
+        let mut len = s.len().min(N);
+        while !s.is_char_boundary(len) {
+            len -= 1;
+        }
+        let mut bytes = [0; N];
+        bytes[..len].copy_from_slice(&s.as_bytes()[..len]);
+        Self { bytes, len }
+    }
+
+    /// Get the string slice.
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).expect("checked on construction")
+    }
+
+    /// Get the bytes of the string.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Get the maximum length in bytes of the string.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Deref for ArrayString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for ArrayString<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for ArrayString<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> Borrow<str> for ArrayString<N> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> fmt::Display for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<ArrayString<M>> for ArrayString<N> {
+    fn eq(&self, other: &ArrayString<M>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> Eq for ArrayString<N> {}
+
+impl<const N: usize> PartialEq<str> for ArrayString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for ArrayString<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> Hash for ArrayString<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+/// Bytes of at most `N` bytes stored inline, see [`decode_to_array`](crate::decode_to_array).
+#[derive(Clone, Copy)]
+pub struct ArrayBytes<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> ArrayBytes<N> {
+    /// Take the first `len` bytes of `bytes`.
+    pub(crate) fn new(bytes: [u8; N], len: usize) -> Self {
+        Self { bytes, len }
+    }
+
+    /// Get the byte slice.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Get the maximum length in bytes.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Deref for ArrayBytes<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for ArrayBytes<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> Borrow<[u8]> for ArrayBytes<N> {
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArrayBytes<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<ArrayBytes<M>> for ArrayBytes<N> {
+    fn eq(&self, other: &ArrayBytes<M>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const N: usize> Eq for ArrayBytes<N> {}
+
+impl<const N: usize> PartialEq<[u8]> for ArrayBytes<N> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<[u8; M]> for ArrayBytes<N> {
+    fn eq(&self, other: &[u8; M]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<const N: usize> Hash for ArrayBytes<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
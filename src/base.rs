@@ -1,11 +1,52 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::array::ArrayString;
+use crate::bases;
+#[cfg(feature = "alloc")]
+use crate::ct;
 use crate::error::{Error, Result};
 use crate::impls::*;
+#[cfg(feature = "alloc")]
+use crate::options::DecodeOptions;
+use crate::spec;
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 use alloc::{string::String, vec::Vec};
 
+/// The family of encodings a [`Base`] belongs to.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum Family {
+    /// The data is kept unmodified.
+    Identity,
+    /// Bit-aligned encodings in the style of RFC 4648, also Base2, Base8 and z-base-32.
+    Rfc4648,
+    /// Radix conversion of the whole input as one number, like base-x.
+    BaseX,
+    /// Base45, RFC 9285.
+    Base45,
+    /// Base256Emoji, one emoji per byte.
+    Emoji,
+    /// Proquint, one pronounceable word per two bytes.
+    Proquint,
+}
+
+/// The status of a [`Base`] in the multibase spec.
+///
+/// The statuses are ordered from the least to the most mature.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Status {
+    /// Experimental, may change or be removed.
+    Experimental,
+    /// Draft, may still change.
+    Draft,
+    /// Final, safe to use for long-lived identifiers.
+    Final,
+}
+
 macro_rules! build_base_enum {
-    ( $(#[$attr:meta] $code:expr => $base:ident,)* ) => {
+    ( $(#[$attr:meta] $code:expr => $base:ident = $name:literal $(| $alias:literal)*,)* ) => {
         /// List of types currently supported in the multibase spec.
         ///
         /// Not all base types are supported by this library.
@@ -14,88 +55,410 @@ macro_rules! build_base_enum {
             $( #[$attr] $base, )*
         }
 
+        $(
+            impl bases::sealed::Sealed for $base {}
+
+            impl bases::TypedBase for $base {
+                const BASE: Base = Base::$base;
+                const CODE: char = $code;
+                const NAME: &'static str = $name;
+                const ALPHABET: Option<&'static str> = <$base as BaseCodec>::ALPHABET;
+            }
+        )*
+
         impl Base {
+            /// All the bases, in the order of the multibase spec table.
+            pub const ALL: &'static [Base] = &[$( Self::$base, )*];
+
+            /// Iterate over all the bases, in the order of the multibase spec table.
+            pub fn iter() -> impl Iterator<Item = Base> {
+                Self::ALL.iter().copied()
+            }
+
             /// Convert a number to the matching base algorithm, or `Error` if no algorithm is matching.
             pub fn from_code(code: char) -> Result<Self> {
         	    match code {
                     $( $code => Ok(Self::$base), )*
+                    _ if spec::is_reserved(code) => Err(Error::ReservedPrefix(code)),
             	    _ => Err(Error::UnknownBase(code)),
         	    }
             }
 
             /// Get the code corresponding to the base algorithm.
-            pub fn code(&self) -> char {
+            pub const fn code(&self) -> char {
                 match self {
                     $( Self::$base => $code, )*
                 }
             }
 
+            /// Get the name of the base algorithm in the multibase spec.
+            pub const fn name(&self) -> &'static str {
+                match self {
+                    $( Self::$base => $name, )*
+                }
+            }
+
+            /// Convert a multibase spec name to the matching base algorithm, or `Error` if no
+            /// algorithm is matching.
+            ///
+            /// Besides the spec names, the lower case variants are also accepted with an explicit
+            /// `lower` suffix, e.g. `base36lower` for `base36`.
+            pub fn from_name(name: &str) -> Result<Self> {
+                match name {
+                    $( $name $(| $alias)* => Ok(Self::$base), )*
+                    _ => Err(Error::UnknownName(ArrayString::truncated(name))),
+                }
+            }
+
+            /// Get the status of the base in the multibase spec.
+            pub const fn status(&self) -> Status {
+                match self {
+                    $( Self::$base => {
+                        const STATUS: Status = spec::status($name);
+                        STATUS
+                    } )*
+                }
+            }
+
+            /// Get the symbols of the alphabet in order of their value, `None` for
+            /// [`Base::Identity`] and [`Base::Proquint`] which has no single alphabet.
+            pub const fn alphabet(&self) -> Option<&'static str> {
+                match self {
+                    $( Self::$base => $base::ALPHABET, )*
+                }
+            }
+
+            /// Get the value of every byte that is a symbol of the alphabet.
+            #[cfg_attr(not(any(feature = "rfc4648", feature = "base-x")), allow(dead_code))]
+            pub(crate) const fn symbol_table(&self) -> &'static [u8; 256] {
+                match self {
+                    $( Self::$base => &$base::SYMBOL_TABLE, )*
+                }
+            }
+
+            /// Get the number of distinct symbol values, 256 for [`Base::Identity`] and 65536
+            /// for the words of [`Base::Proquint`].
+            pub const fn radix(&self) -> u32 {
+                match self {
+                    $( Self::$base => $base::RADIX, )*
+                }
+            }
+
+            /// Get the padding symbol, if the base is padded.
+            pub const fn padding(&self) -> Option<char> {
+                match self {
+                    $( Self::$base => $base::PADDING, )*
+                }
+            }
+
+            /// Check whether letters are decoded regardless of their case.
+            pub const fn is_case_insensitive(&self) -> bool {
+                match self {
+                    $( Self::$base => $base::CASE_INSENSITIVE, )*
+                }
+            }
+
+            /// Get the family of encodings of the base.
+            pub const fn family(&self) -> Family {
+                match self {
+                    $( Self::$base => $base::FAMILY, )*
+                }
+            }
+
+            /// Get the number of input bytes and output symbols of a block, `None` if the base
+            /// is not block-aligned.
+            ///
+            /// The symbols of the block-aligned bases are one byte long. Base256Emoji is not
+            /// block-aligned, its symbols are 3 or 4 bytes long.
+            pub const fn block_size(&self) -> Option<(usize, usize)> {
+                match self {
+                    $( Self::$base => $base::BLOCK_SIZE, )*
+                }
+            }
+
             /// Encode the given byte slice to base string.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the base is [`Base::Identity`] and the input is not valid UTF-8, or if
+            /// the base is not supported, see [`Base::is_supported`]. Use [`Base::try_encode`]
+            /// to handle these cases.
+            #[cfg(feature = "alloc")]
             pub fn encode<I: AsRef<[u8]>>(&self, input: I) -> String {
-                match self {
-                    $( Self::$base => $base::encode(input), )*
+                match self.try_encode(input) {
+                    Err(Error::Unsupported(base)) => panic!("unsupported base: {:?}", base),
+                    encoded => encoded.expect("input must be valid UTF-8 bytes"),
                 }
             }
 
+            /// Encode the given byte slice to base string, or `Error` if the base is
+            /// [`Base::Identity`] and the input is not valid UTF-8, or if the base is not
+            /// supported.
+            #[cfg(feature = "alloc")]
+            pub fn try_encode<I: AsRef<[u8]>>(&self, input: I) -> Result<String> {
+                let input = input.as_ref();
+                if !self.is_supported() {
+                    return Err(Error::Unsupported(*self));
+                }
+                if *self == Self::Identity {
+                    if let Err(err) = core::str::from_utf8(input) {
+                        return Err(Error::InvalidUtf8 {
+                            position: err.valid_up_to(),
+                        });
+                    }
+                }
+                Ok(match self {
+                    $( Self::$base => $base::encode(input), )*
+                })
+            }
+
             /// Decode the base string.
+            ///
+            /// The size of the input is not limited, use [`Base::decode_with_options`] with
+            /// [`DecodeLimits`](crate::DecodeLimits) for untrusted inputs.
+            #[cfg(feature = "alloc")]
             pub fn decode<I: AsRef<str>>(&self, input: I) -> Result<Vec<u8>> {
                 match self {
                     $( Self::$base => $base::decode(input), )*
                 }
             }
+
+            /// Decode the base string with the given options.
+            #[cfg(feature = "alloc")]
+            pub fn decode_with_options<I: AsRef<str>>(
+                &self,
+                input: I,
+                options: &DecodeOptions,
+            ) -> Result<Vec<u8>> {
+                options.decode_base(*self, input.as_ref())
+            }
+
+            /// Get the length in bytes of the base string encoding `input_len` bytes.
+            ///
+            /// The length is exact for the block-aligned bases, including Base45 and Identity,
+            /// see [`Base::block_size`]. For the other bases, like Base58Btc and Base256Emoji,
+            /// it depends on the input, and this is an upper bound.
+            ///
+            /// Returns [`Error::Unsupported`] if the base is not supported.
+            pub fn encoded_len(&self, input_len: usize) -> Result<usize> {
+                if !self.is_supported() {
+                    return Err(Error::Unsupported(*self));
+                }
+                Ok(match self {
+                    $( Self::$base => $base::encoded_len(input_len), )*
+                })
+            }
+
+            /// Get an upper bound of the number of bytes decoded from a base string of
+            /// `encoded_len` bytes.
+            ///
+            /// Returns [`Error::Unsupported`] if the base is not supported.
+            pub fn decoded_len_max(&self, encoded_len: usize) -> Result<usize> {
+                if !self.is_supported() {
+                    return Err(Error::Unsupported(*self));
+                }
+                Ok(match self {
+                    $( Self::$base => $base::decoded_len_max(encoded_len), )*
+                })
+            }
+
+            /// Encode the given byte slice into `output`, returns the number of bytes written.
+            ///
+            /// Returns [`Error::BufferTooSmall`] if `output` is shorter than the encoded
+            /// string, [`Base::encoded_len`] gives a length that is always large enough.
+            pub fn encode_to_slice<I: AsRef<[u8]>>(&self, input: I, output: &mut [u8]) -> Result<usize> {
+                match self {
+                    $( Self::$base => $base::encode_to_slice(input.as_ref(), output), )*
+                }
+            }
+
+            /// Decode the base string into `output`, returns the number of bytes written.
+            ///
+            /// Returns [`Error::BufferTooSmall`] if `output` is shorter than the decoded
+            /// data, [`Base::decoded_len_max`] gives a length that is always large enough.
+            pub fn decode_to_slice<I: AsRef<str>>(&self, input: I, output: &mut [u8]) -> Result<usize> {
+                match self {
+                    $( Self::$base => $base::decode_to_slice(input.as_ref(), output), )*
+                }
+            }
+
+            /// Check the base string contains only symbols of the alphabet, and has a valid
+            /// length where the base constrains it.
+            #[cfg(feature = "alloc")]
+            pub(crate) fn validate(&self, input: &str) -> Result<()> {
+                match self {
+                    $( Self::$base => $base::validate(input), )*
+                }
+            }
+
+            /// Get the block-aligned encoding backing this base, if any.
+            #[cfg(all(feature = "std", feature = "rfc4648"))]
+            pub(crate) fn encoding(&self) -> Option<data_encoding::Encoding> {
+                match self {
+                    $( Self::$base => $base::ENCODING, )*
+                }
+            }
         }
     }
 }
 
 build_base_enum! {
     /// 8-bit binary (encoder and decoder keeps data unmodified).
-    '\x00' => Identity,
+    '\x00' => Identity = "identity",
     /// Base2 (alphabet: 01).
-    '0' => Base2,
+    '0' => Base2 = "base2",
     /// Base8 (alphabet: 01234567).
-    '7' => Base8,
+    '7' => Base8 = "base8",
     /// Base10 (alphabet: 0123456789).
-    '9' => Base10,
+    '9' => Base10 = "base10",
     /// Base16 lower hexadecimal (alphabet: 0123456789abcdef).
-    'f' => Base16Lower,
+    'f' => Base16Lower = "base16" | "base16lower",
     /// Base16 upper hexadecimal (alphabet: 0123456789ABCDEF).
-    'F' => Base16Upper,
+    'F' => Base16Upper = "base16upper",
      /// Base32, rfc4648 no padding (alphabet: abcdefghijklmnopqrstuvwxyz234567).
-    'b' => Base32Lower,
+    'b' => Base32Lower = "base32" | "base32lower",
     /// Base32, rfc4648 no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZ234567).
-    'B' => Base32Upper,
+    'B' => Base32Upper = "base32upper",
     /// Base32, rfc4648 with padding (alphabet: abcdefghijklmnopqrstuvwxyz234567).
-    'c' => Base32PadLower,
+    'c' => Base32PadLower = "base32pad" | "base32padlower",
     /// Base32, rfc4648 with padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZ234567).
-    'C' => Base32PadUpper,
+    'C' => Base32PadUpper = "base32padupper",
     /// Base32hex, rfc4648 no padding (alphabet: 0123456789abcdefghijklmnopqrstuv).
-    'v' => Base32HexLower,
+    'v' => Base32HexLower = "base32hex" | "base32hexlower",
     /// Base32hex, rfc4648 no padding (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUV).
-    'V' => Base32HexUpper,
+    'V' => Base32HexUpper = "base32hexupper",
     /// Base32hex, rfc4648 with padding (alphabet: 0123456789abcdefghijklmnopqrstuv).
-    't' => Base32HexPadLower,
+    't' => Base32HexPadLower = "base32hexpad" | "base32hexpadlower",
     /// Base32hex, rfc4648 with padding (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUV).
-    'T' => Base32HexPadUpper,
+    'T' => Base32HexPadUpper = "base32hexpadupper",
     /// z-base-32 (used by Tahoe-LAFS) (alphabet: ybndrfg8ejkmcpqxot1uwisza345h769).
-    'h' => Base32Z,
+    'h' => Base32Z = "base32z",
     /// Base36, [0-9a-z] no padding (alphabet: 0123456789abcdefghijklmnopqrstuvwxyz).
-    'k' => Base36Lower,
+    'k' => Base36Lower = "base36" | "base36lower",
     /// Base36, [0-9A-Z] no padding (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ).
-    'K' => Base36Upper,
+    'K' => Base36Upper = "base36upper",
     /// Base45, rfc9285 (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:).
-    'R' => Base45,
+    'R' => Base45 = "base45",
     /// Base58 flicker (alphabet: 123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ).
-    'Z' => Base58Flickr,
+    'Z' => Base58Flickr = "base58flickr",
     /// Base58 bitcoin (alphabet: 123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz).
-    'z' => Base58Btc,
+    'z' => Base58Btc = "base58btc",
     /// Base64, rfc4648 no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/).
-    'm' => Base64,
+    'm' => Base64 = "base64",
     /// Base64, rfc4648 with padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/).
-    'M' => Base64Pad,
+    'M' => Base64Pad = "base64pad",
     /// Base64 url, rfc4648 no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_).
-    'u' => Base64Url,
+    'u' => Base64Url = "base64url",
     /// Base64 url, rfc4648 with padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_).
-    'U' => Base64UrlPad,
+    'U' => Base64UrlPad = "base64urlpad",
+    /// Proquint, pronounceable quintuplets (consonants: bdfghjklmnprstvz, vowels: aiou).
+    'p' => Proquint = "proquint",
 /// Base256Emoji (alphabet: 🚀🪐☄🛰🌌🌑🌒🌓🌔🌕🌖🌗🌘🌍🌏🌎🐉☀💻🖥💾💿😂❤😍🤣😊🙏💕😭😘👍😅👏😁🔥🥰💔💖💙😢🤔😆🙄💪😉☺👌🤗💜😔😎😇🌹🤦🎉💞✌✨🤷😱😌🌸🙌😋💗💚😏💛🙂💓🤩😄😀🖤😃💯🙈👇🎶😒🤭❣😜💋👀😪😑💥🙋😞😩😡🤪👊🥳😥🤤👉💃😳✋😚😝😴🌟😬🙃🍀🌷😻😓⭐✅🥺🌈😈🤘💦✔😣🏃💐☹🎊💘😠☝😕🌺🎂🌻😐🖕💝🙊😹🗣💫💀👑🎵🤞😛🔴😤🌼😫⚽🤙☕🏆🤫👈😮🙆🍻🍃🐶💁😲🌿🧡🎁⚡🌞🎈❌✊👋😰🤨😶🤝🚶💰🍓💢🤟🙁🚨💨🤬✈🎀🍺🤓😙💟🌱😖👶🥴▶➡❓💎💸⬇😨🌚🦋😷🕺⚠🙅😟😵👎🤲🤠🤧📌🔵💅🧐🐾🍒😗🤑🌊🤯🐷☎💧😯💆👆🎤🙇🍑❄🌴💣🐸💌📍🥀🤢👅💡💩👐📸👻🤐🤮🎼🥵🚩🍎🍊👼💍📣🥂)
-    '🚀' => Base256Emoji,
+    '🚀' => Base256Emoji = "base256emoji",
+}
+
+impl Base {
+    /// Check whether the cargo feature implementing the base is enabled.
+    ///
+    /// The other bases are still recognized, but return [`Error::Unsupported`] when encoding,
+    /// decoding or computing lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use multibase::Base;
+    ///
+    /// assert!(Base::Identity.is_supported());
+    /// assert_eq!(Base::Base58Btc.is_supported(), cfg!(feature = "base-x"));
+    /// ```
+    pub const fn is_supported(&self) -> bool {
+        match self.family() {
+            Family::Identity | Family::Proquint => true,
+            Family::Rfc4648 => cfg!(feature = "rfc4648"),
+            Family::BaseX => cfg!(feature = "base-x"),
+            Family::Base45 => cfg!(feature = "base45"),
+            Family::Emoji => cfg!(feature = "emoji"),
+        }
+    }
+
+    /// Decode the base string, rejecting it unless it is the unique encoding of its bytes.
+    ///
+    /// Non-canonical strings decode fine with [`Base::decode`], e.g. with the other case of a
+    /// case-insensitive base.
+    #[cfg(feature = "alloc")]
+    pub fn decode_canonical<I: AsRef<str>>(&self, input: I) -> Result<Vec<u8>> {
+        let input = input.as_ref();
+        let decoded = self.decode(input)?;
+        let encoded = self.encode(&decoded);
+        if encoded != input {
+            let position = input
+                .bytes()
+                .zip(encoded.bytes())
+                .position(|(a, b)| a != b)
+                .unwrap_or_else(|| input.len().min(encoded.len()));
+            return Err(Error::NonCanonical {
+                base: *self,
+                position,
+            });
+        }
+        Ok(decoded)
+    }
+
+    /// Check that the base string decodes and is the unique encoding of its bytes.
+    #[cfg(feature = "alloc")]
+    pub fn is_canonical<I: AsRef<str>>(&self, input: I) -> bool {
+        self.decode_canonical(input).is_ok()
+    }
+
+    /// Encode the given byte slice to base string in constant time, for secret material.
+    ///
+    /// Only the bit-aligned bases are supported, i.e. the Base16, Base32 and Base64 variants
+    /// as well as Base2 and Base8, the other bases return [`Error::Unsupported`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use multibase::Base;
+    ///
+    /// let key = [0xde, 0xad, 0xbe, 0xef];
+    /// assert_eq!(Base::Base64Pad.encode_ct(key).unwrap(), Base::Base64Pad.encode(key));
+    /// assert!(Base::Base58Btc.encode_ct(key).is_err());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encode_ct<I: AsRef<[u8]>>(&self, input: I) -> Result<String> {
+        ct::encode(*self, input.as_ref())
+    }
+
+    /// Decode the base string in constant time, for secret material.
+    ///
+    /// Accepts the same strings as [`Base::decode`], for the bases supported by
+    /// [`Base::encode_ct`]. Only the length of the input and whether it is valid are leaked,
+    /// the error of an invalid input is not computed in constant time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use multibase::{ct_eq, Base};
+    ///
+    /// let key = Base::Base16Lower.decode_ct("deadbeef").unwrap();
+    /// assert!(ct_eq(&key, &[0xde, 0xad, 0xbe, 0xef]));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decode_ct<I: AsRef<str>>(&self, input: I) -> Result<Vec<u8>> {
+        ct::decode(*self, input.as_ref())
+    }
+}
+
+impl FromStr for Base {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Self::from_name(name)
+    }
+}
+
+impl fmt::Display for Base {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
 }
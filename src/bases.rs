@@ -0,0 +1,41 @@
+//! The bases as types, to select a base at compile time, e.g. with [`TypedMultibase`].
+//!
+//! [`TypedMultibase`]: crate::TypedMultibase
+
+use crate::base::Base;
+
+pub use crate::impls::{
+    Base10, Base16Lower, Base16Upper, Base2, Base256Emoji, Base32HexLower, Base32HexPadLower,
+    Base32HexPadUpper, Base32HexUpper, Base32Lower, Base32PadLower, Base32PadUpper, Base32Upper,
+    Base32Z, Base36Lower, Base36Upper, Base45, Base58Btc, Base58Flickr, Base64, Base64Pad,
+    Base64Url, Base64UrlPad, Base8, Identity, Proquint,
+};
+
+/// A base selected at compile time, implemented by the types of [`bases`](crate::bases).
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::bases::Base58Btc;
+/// use multibase::{Base, TypedBase};
+///
+/// assert_eq!(Base58Btc::BASE, Base::Base58Btc);
+/// assert_eq!(Base58Btc::CODE, 'z');
+/// assert_eq!(Base58Btc::NAME, "base58btc");
+/// ```
+pub trait TypedBase: sealed::Sealed + Copy + 'static {
+    /// The matching [`Base`].
+    const BASE: Base;
+    /// The base code.
+    const CODE: char;
+    /// The name of the base in the multibase spec.
+    const NAME: &'static str;
+    /// The symbols of the alphabet in order of their value, see [`Base::alphabet`].
+    const ALPHABET: Option<&'static str>;
+}
+
+pub(crate) mod sealed {
+    pub trait Sealed {}
+}
@@ -0,0 +1,439 @@
+//! Arbitrary-precision unsigned integers for the radix conversion of the bases that are not
+//! block-aligned.
+//!
+//! Numbers are little-endian vectors of `u64` limbs. Multiplication switches to Karatsuba for
+//! large operands and division uses Barrett reduction with a reciprocal computed by Newton
+//! iteration, the conversion from and to digits splits the number in halves. Together this
+//! makes the conversion sub-quadratic.
+
+use core::cmp::Ordering;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{vec, vec::Vec};
+
+/// Below this number of limbs, the schoolbook multiplication is used.
+const KARATSUBA_THRESHOLD: usize = 48;
+
+/// Below this number of limbs or digits, numbers are converted one digit at a time.
+const CONVERSION_THRESHOLD: usize = 64;
+
+/// Convert big-endian bytes to a number.
+pub(crate) fn from_be_bytes(bytes: &[u8]) -> Vec<u64> {
+    let mut limbs: Vec<u64> = bytes
+        .rchunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(0, |limb, &byte| limb << 8 | u64::from(byte))
+        })
+        .collect();
+    normalize(&mut limbs);
+    limbs
+}
+
+/// Append the big-endian bytes of `a`, without leading zeros, to `output`.
+pub(crate) fn extend_be_bytes(a: &[u64], output: &mut Vec<u8>) {
+    let a = trim(a);
+    let Some((&high, rest)) = a.split_last() else {
+        return;
+    };
+    let high = high.to_be_bytes();
+    let zeros = high.iter().take_while(|&&byte| byte == 0).count();
+    output.extend_from_slice(&high[zeros..]);
+    for limb in rest.iter().rev() {
+        output.extend_from_slice(&limb.to_be_bytes());
+    }
+}
+
+/// Convert `a` to digits in radix `big`, the most significant first, without leading zeros.
+pub(crate) fn to_digits(mut a: Vec<u64>, big: u64) -> Vec<u64> {
+    normalize(&mut a);
+    if a.len() <= CONVERSION_THRESHOLD {
+        let mut digits = Vec::new();
+        while !a.is_empty() {
+            digits.push(div_small(&mut a, big));
+        }
+        digits.reverse();
+        return digits;
+    }
+
+    // `powers[i]` is `big^(2^i)`, up to the first power larger than `a`.
+    let mut powers = vec![vec![big]];
+    while let Some(last) = powers
+        .last()
+        .filter(|last| cmp(last, &a) != Ordering::Greater)
+    {
+        let square = mul(last, last);
+        powers.push(square);
+    }
+    let level = powers.len() - 2;
+    let mut reciprocals = vec![None; powers.len()];
+    let mut digits = vec![0; 2 << level];
+    to_digits_split(a, level, &powers, &mut reciprocals, &mut digits);
+    let zeros = digits.iter().take_while(|&&digit| digit == 0).count();
+    digits.drain(..zeros);
+    digits
+}
+
+/// Write the `2^(level + 1)` digits of `a`, which is smaller than `powers[level]^2`.
+fn to_digits_split(
+    mut a: Vec<u64>,
+    level: usize,
+    powers: &[Vec<u64>],
+    reciprocals: &mut [Option<Vec<u64>>],
+    digits: &mut [u64],
+) {
+    if level == 0 || a.len() <= CONVERSION_THRESHOLD {
+        for digit in digits.iter_mut().rev() {
+            if a.is_empty() {
+                break;
+            }
+            *digit = div_small(&mut a, powers[0][0]);
+        }
+        return;
+    }
+
+    let power = &powers[level];
+    if reciprocals[level].is_none() {
+        reciprocals[level] = Some(reciprocal(power));
+    }
+    let (quotient, remainder) = div_rem(&a, power, reciprocals[level].as_deref().unwrap());
+    let (high, low) = digits.split_at_mut(digits.len() / 2);
+    to_digits_split(quotient, level - 1, powers, reciprocals, high);
+    to_digits_split(remainder, level - 1, powers, reciprocals, low);
+}
+
+/// Convert digits in radix `big`, the most significant first, to a number.
+pub(crate) fn from_digits(digits: &[u64], big: u64) -> Vec<u64> {
+    // `powers[i]` is `big^(2^i)`, up to the largest power with fewer digits than `digits`.
+    let mut powers = vec![vec![big]];
+    while CONVERSION_THRESHOLD < digits.len() && 1 << powers.len() < digits.len() {
+        let last = &powers[powers.len() - 1];
+        let square = mul(last, last);
+        powers.push(square);
+    }
+    from_digits_split(digits, &powers)
+}
+
+fn from_digits_split(digits: &[u64], powers: &[Vec<u64>]) -> Vec<u64> {
+    if digits.len() <= CONVERSION_THRESHOLD {
+        let mut a = Vec::with_capacity(digits.len());
+        for &digit in digits {
+            mul_small_add(&mut a, powers[0][0], digit);
+        }
+        return a;
+    }
+
+    let level = (usize::BITS - (digits.len() - 1).leading_zeros() - 1) as usize;
+    let (high, low) = digits.split_at(digits.len() - (1 << level));
+    let mut a = mul(&from_digits_split(high, powers), &powers[level]);
+    add_shifted(&mut a, &from_digits_split(low, powers), 0);
+    a
+}
+
+/// Remove the high zero limbs.
+fn trim(a: &[u64]) -> &[u64] {
+    let len = a
+        .iter()
+        .rposition(|&limb| limb != 0)
+        .map_or(0, |index| index + 1);
+    &a[..len]
+}
+
+fn normalize(a: &mut Vec<u64>) {
+    let len = trim(a).len();
+    a.truncate(len);
+}
+
+fn cmp(a: &[u64], b: &[u64]) -> Ordering {
+    let (a, b) = (trim(a), trim(b));
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+/// Get `a >> (64 * limbs)`.
+fn shr_limbs(a: &[u64], limbs: usize) -> Vec<u64> {
+    a.get(limbs..).map_or_else(Vec::new, <[u64]>::to_vec)
+}
+
+/// Get `2^(64 * limbs)`.
+fn pow_limbs(limbs: usize) -> Vec<u64> {
+    let mut a = vec![0; limbs + 1];
+    a[limbs] = 1;
+    a
+}
+
+/// Compute `a += b << (64 * shift)`.
+fn add_shifted(a: &mut Vec<u64>, b: &[u64], shift: usize) {
+    let b = trim(b);
+    if b.is_empty() {
+        return;
+    }
+    if a.len() < shift + b.len() {
+        a.resize(shift + b.len(), 0);
+    }
+    let mut carry = false;
+    for (limb, &other) in a[shift..].iter_mut().zip(b) {
+        let (sum, overflow) = limb.overflowing_add(other);
+        let (sum, carried) = sum.overflowing_add(u64::from(carry));
+        *limb = sum;
+        carry = overflow || carried;
+    }
+    for limb in &mut a[shift + b.len()..] {
+        if !carry {
+            break;
+        }
+        let (sum, overflow) = limb.overflowing_add(1);
+        *limb = sum;
+        carry = overflow;
+    }
+    if carry {
+        a.push(1);
+    }
+}
+
+/// Compute `a -= b`, `a` must not be smaller than `b`.
+fn sub_assign(a: &mut Vec<u64>, b: &[u64]) {
+    let b = trim(b);
+    debug_assert!(cmp(a, b) != Ordering::Less);
+    let mut borrow = false;
+    for (index, limb) in a.iter_mut().enumerate() {
+        if index >= b.len() && !borrow {
+            break;
+        }
+        let other = b.get(index).copied().unwrap_or_default();
+        let (difference, overflow) = limb.overflowing_sub(other);
+        let (difference, borrowed) = difference.overflowing_sub(u64::from(borrow));
+        *limb = difference;
+        borrow = overflow || borrowed;
+    }
+    normalize(a);
+}
+
+/// Compute `a = a * m + add`.
+fn mul_small_add(a: &mut Vec<u64>, m: u64, add: u64) {
+    let mut carry = u128::from(add);
+    for limb in a.iter_mut() {
+        let product = u128::from(*limb) * u128::from(m) + carry;
+        *limb = product as u64;
+        carry = product >> 64;
+    }
+    if carry > 0 {
+        a.push(carry as u64);
+    }
+}
+
+/// Compute `a /= d`, returns the remainder.
+fn div_small(a: &mut Vec<u64>, d: u64) -> u64 {
+    let d = u128::from(d);
+    let mut remainder = 0;
+    for limb in a.iter_mut().rev() {
+        let current = remainder << 64 | u128::from(*limb);
+        *limb = (current / d) as u64;
+        remainder = current % d;
+    }
+    normalize(a);
+    remainder as u64
+}
+
+fn mul(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = (trim(a), trim(b));
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    if b.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![0; a.len() + b.len()];
+    mul_into(&mut product, a, b);
+    normalize(&mut product);
+    product
+}
+
+/// Compute `product = a * b`, where `a` is not shorter than `b` and `product` has the length of
+/// both.
+fn mul_into(product: &mut [u64], a: &[u64], b: &[u64]) {
+    if b.len() < KARATSUBA_THRESHOLD {
+        mul_schoolbook(product, a, b);
+        return;
+    }
+    if a.len() >= 2 * b.len() {
+        product.fill(0);
+        let mut chunk_product = vec![0; 2 * b.len()];
+        for (index, chunk) in a.chunks(b.len()).enumerate() {
+            let chunk_product = &mut chunk_product[..chunk.len() + b.len()];
+            if chunk.len() < b.len() {
+                mul_into(chunk_product, b, chunk);
+            } else {
+                mul_into(chunk_product, chunk, b);
+            }
+            add_into(&mut product[index * b.len()..], chunk_product);
+        }
+        return;
+    }
+
+    // Karatsuba: (a1 x + a0)(b1 x + b0) = z2 x^2 + z1 x + z0, with
+    // z1 = (a1 + a0)(b1 + b0) - z2 - z0.
+    let half = a.len() / 2;
+    let (a0, a1) = a.split_at(half);
+    let (b0, b1) = b.split_at(half);
+    let mut a_sum = vec![0; a1.len() + 1];
+    a_sum[..a1.len()].copy_from_slice(a1);
+    add_into(&mut a_sum, a0);
+    let mut b_sum = vec![0; half.max(b1.len()) + 1];
+    b_sum[..b1.len()].copy_from_slice(b1);
+    add_into(&mut b_sum, b0);
+    let mut middle = vec![0; a_sum.len() + b_sum.len()];
+    mul_into(&mut middle, &a_sum, &b_sum);
+
+    let (low, high) = product.split_at_mut(2 * half);
+    mul_into(low, a0, b0);
+    mul_into(high, a1, b1);
+    sub_into(&mut middle, low);
+    sub_into(&mut middle, high);
+    add_into(&mut product[half..], trim(&middle));
+}
+
+fn mul_schoolbook(product: &mut [u64], a: &[u64], b: &[u64]) {
+    product.fill(0);
+    for (index, &m) in b.iter().enumerate() {
+        let mut carry = 0;
+        for (limb, &other) in product[index..].iter_mut().zip(a) {
+            let sum = u128::from(other) * u128::from(m) + u128::from(*limb) + carry;
+            *limb = sum as u64;
+            carry = sum >> 64;
+        }
+        product[index + a.len()] = carry as u64;
+    }
+}
+
+/// Compute `a += b`, `a` must be large enough for the sum.
+fn add_into(a: &mut [u64], b: &[u64]) {
+    let mut carry = false;
+    for (limb, &other) in a.iter_mut().zip(b) {
+        let (sum, overflow) = limb.overflowing_add(other);
+        let (sum, carried) = sum.overflowing_add(u64::from(carry));
+        *limb = sum;
+        carry = overflow || carried;
+    }
+    for limb in &mut a[b.len()..] {
+        if !carry {
+            break;
+        }
+        let (sum, overflow) = limb.overflowing_add(1);
+        *limb = sum;
+        carry = overflow;
+    }
+    debug_assert!(!carry);
+}
+
+/// Compute `a -= b`, `a` must not be smaller than `b`.
+fn sub_into(a: &mut [u64], b: &[u64]) {
+    let mut borrow = false;
+    for (limb, &other) in a.iter_mut().zip(b) {
+        let (difference, overflow) = limb.overflowing_sub(other);
+        let (difference, borrowed) = difference.overflowing_sub(u64::from(borrow));
+        *limb = difference;
+        borrow = overflow || borrowed;
+    }
+    for limb in &mut a[b.len()..] {
+        if !borrow {
+            break;
+        }
+        let (difference, overflow) = limb.overflowing_sub(1);
+        *limb = difference;
+        borrow = overflow;
+    }
+    debug_assert!(!borrow);
+}
+
+/// Get `a << (64 * limbs)`.
+fn shl_limbs(a: &[u64], limbs: usize) -> Vec<u64> {
+    let mut shifted = vec![0; limbs];
+    shifted.extend_from_slice(a);
+    shifted
+}
+
+/// Get `floor(2^(128 n) / d)`, where `d` has `n` limbs.
+fn reciprocal(d: &[u64]) -> Vec<u64> {
+    let n = d.len();
+    if n <= 4 {
+        return reciprocal_small(d);
+    }
+
+    // Start from the reciprocal of the top half of the divisor, rounded up, which
+    // underestimates the reciprocal: `d < (top + 1) 2^(64 (n - h))`.
+    let h = n / 2 + 2;
+    let mut top = d[n - h..].to_vec();
+    add_shifted(&mut top, &[1], 0);
+    let estimate = if top.len() > h {
+        pow_limbs(h)
+    } else {
+        reciprocal(&top)
+    };
+    let mut x = shl_limbs(&estimate, n - h);
+    let mut error = pow_limbs(2 * n);
+    sub_assign(&mut error, &shl_limbs(&mul(d, &estimate), n - h));
+
+    // One Newton step `x += x error / 2^(128 n)` doubles the precision, truncating the operands
+    // to the limbs that matter keeps it an underestimate within a few units.
+    let increment = shr_limbs(
+        &mul(&shr_limbs(&x, h - 2), &shr_limbs(&error, n - 2)),
+        n - h + 4,
+    );
+    add_shifted(&mut x, &increment, 0);
+    sub_assign(&mut error, &mul(d, &increment));
+    while cmp(&error, d) != Ordering::Less {
+        sub_assign(&mut error, d);
+        add_shifted(&mut x, &[1], 0);
+    }
+    x
+}
+
+/// Get `floor(2^(128 n) / d)`, where `d` has at most 4 limbs.
+fn reciprocal_small(d: &[u64]) -> Vec<u64> {
+    let n = d.len();
+    // With `top` the top two limbs, `d < (top + 1) 2^(64 (n - 2))`.
+    let top = d[n.saturating_sub(2)..]
+        .iter()
+        .rev()
+        .fold(0, |top, &limb| top << 64 | u128::from(limb));
+    let (estimate, shift) = if n == 1 {
+        (u128::MAX / top, 0)
+    } else {
+        (top.checked_add(1).map_or(1, |top| u128::MAX / top), n)
+    };
+    let mut x = shl_limbs(&[estimate as u64, (estimate >> 64) as u64], shift);
+    normalize(&mut x);
+
+    // Newton iterations `x += x error / 2^(128 n)` converge from below.
+    let r = pow_limbs(2 * n);
+    loop {
+        let mut error = r.clone();
+        sub_assign(&mut error, &mul(d, &x));
+        let increment = shr_limbs(&mul(&x, &error), 2 * n);
+        if trim(&increment).is_empty() {
+            while cmp(&error, d) != Ordering::Less {
+                sub_assign(&mut error, d);
+                add_shifted(&mut x, &[1], 0);
+            }
+            return x;
+        }
+        add_shifted(&mut x, &increment, 0);
+    }
+}
+
+/// Divide `a` by `d`, given `reciprocal = reciprocal(d)` and `a < 2^(128 n)` where `d` has `n`
+/// limbs, returns the quotient and the remainder.
+fn div_rem(a: &[u64], d: &[u64], reciprocal: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    let n = d.len();
+    // Barrett reduction, the estimated quotient is at most 2 below the quotient.
+    let mut quotient = shr_limbs(&mul(&shr_limbs(a, n - 1), reciprocal), n + 1);
+    normalize(&mut quotient);
+    let mut remainder = trim(a).to_vec();
+    sub_assign(&mut remainder, &mul(&quotient, d));
+    while cmp(&remainder, d) != Ordering::Less {
+        sub_assign(&mut remainder, d);
+        add_shifted(&mut quotient, &[1], 0);
+    }
+    (quotient, remainder)
+}
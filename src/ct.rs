@@ -0,0 +1,156 @@
+//! Constant-time encoding and decoding of the bit-aligned bases, for secret material.
+//!
+//! Symbols are mapped to values by scanning the whole alphabet with masks instead of indexing
+//! a lookup table, and no branch depends on the value of a symbol or a byte. The length of the
+//! data and the position of the padding are not considered secret.
+
+use core::hint::black_box;
+
+#[cfg(feature = "alloc")]
+use crate::base::{Base, Family};
+#[cfg(feature = "alloc")]
+use crate::error::{Error, Result};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+
+/// Compare two byte slices in constant time.
+///
+/// The time taken depends on the lengths of the slices, but not on their contents.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::ct_eq;
+///
+/// assert!(ct_eq(b"secret", b"secret"));
+/// assert!(!ct_eq(b"secret", b"secreT"));
+/// assert!(!ct_eq(b"secret", b"secrets"));
+/// ```
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b));
+    black_box(diff) == 0
+}
+
+/// Get `0xff` if `a == b`, `0` otherwise.
+#[cfg(feature = "alloc")]
+fn eq_mask(a: u8, b: u8) -> u8 {
+    let diff = u32::from(a ^ b);
+    black_box((diff.wrapping_sub(1) >> 8) as u8)
+}
+
+/// Get the alphabet and the bit width of `base`, if it is supported.
+#[cfg(feature = "alloc")]
+fn alphabet(base: Base) -> Result<(&'static [u8], usize)> {
+    match (base.family(), base.alphabet()) {
+        (Family::Rfc4648, Some(alphabet)) if base.is_supported() => {
+            Ok((alphabet.as_bytes(), base.radix().trailing_zeros() as usize))
+        }
+        _ => Err(Error::Unsupported(base)),
+    }
+}
+
+/// Encode `input` with `base` in constant time, see [`Base::encode_ct`].
+#[cfg(feature = "alloc")]
+pub(crate) fn encode(base: Base, input: &[u8]) -> Result<String> {
+    let (alphabet, bit) = alphabet(base)?;
+    let mut output = Vec::with_capacity(base.encoded_len(input.len())?);
+
+    let symbol = |value: u8| {
+        alphabet.iter().zip(0..).fold(0, |symbol, (&char, index)| {
+            symbol | (char & eq_mask(value, index))
+        })
+    };
+    let mask = (1 << bit) - 1;
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in input {
+        buffer = buffer << 8 | u32::from(byte);
+        bits += 8;
+        while bits >= bit {
+            bits -= bit;
+            output.push(symbol((buffer >> bits) as u8 & mask));
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        output.push(symbol((buffer << (bit - bits)) as u8 & mask));
+    }
+    if let (Some(pad), Some((_, symbols))) = (base.padding(), base.block_size()) {
+        while !output.len().is_multiple_of(symbols) {
+            output.push(pad as u8);
+        }
+    }
+    Ok(String::from_utf8(output).expect("alphabets are ASCII"))
+}
+
+/// Decode `input` with `base` in constant time, see [`Base::decode_ct`].
+#[cfg(feature = "alloc")]
+pub(crate) fn decode(base: Base, input: &str) -> Result<Vec<u8>> {
+    let (alphabet, bit) = alphabet(base)?;
+    let bytes = input.as_bytes();
+    // The structure of the input is public, a malformed input is decoded again to locate the
+    // error, `expected` is only returned if the decoders disagree.
+    let malformed = |expected: Error| base.decode(input).err().unwrap_or(expected);
+    let invalid_length = |len: usize| malformed(Error::InvalidLength { base, len });
+
+    let mut end = bytes.len();
+    if let (Some(pad), Some((_, symbols))) = (base.padding(), base.block_size()) {
+        if !end.is_multiple_of(symbols) {
+            return Err(invalid_length(end));
+        }
+        while end > 0 && bytes[end - 1] == pad as u8 {
+            end -= 1;
+        }
+        if end < bytes.len() && end.is_multiple_of(symbols) {
+            return Err(malformed(Error::InvalidPadding {
+                base,
+                position: end,
+            }));
+        }
+    }
+    if end * bit % 8 >= bit {
+        return Err(invalid_length(bytes.len()));
+    }
+
+    let case_insensitive = base.is_case_insensitive();
+    let value = |symbol: u8| {
+        alphabet
+            .iter()
+            .zip(0..)
+            .fold((0, 0), |(value, valid), (&char, index)| {
+                let mut mask = eq_mask(symbol, char);
+                if case_insensitive && char.is_ascii_alphabetic() {
+                    mask |= eq_mask(symbol | 0x20, char | 0x20);
+                }
+                (value | (index & mask), valid | mask)
+            })
+    };
+    let mut output = Vec::with_capacity(end * bit / 8);
+    let mut invalid = 0;
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &symbol in &bytes[..end] {
+        let (value, valid) = value(symbol);
+        invalid |= !valid;
+        buffer = buffer << bit | u32::from(value);
+        bits += bit;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    // Non-zero trailing bits.
+    invalid |= black_box(buffer.wrapping_neg() >> 24) as u8;
+    if black_box(invalid) != 0 {
+        return Err(malformed(Error::TrailingBits {
+            base,
+            position: end.saturating_sub(1),
+        }));
+    }
+    Ok(output)
+}
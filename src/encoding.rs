@@ -1,12 +1,16 @@
+#[cfg(feature = "rfc4648")]
 use data_encoding::Encoding;
+#[cfg(feature = "rfc4648")]
 use data_encoding_macro::new_encoding;
 
 // Base2 (alphabet: 01)
+#[cfg(feature = "rfc4648")]
 pub const BASE2: Encoding = new_encoding! {
     symbols: "01",
 };
 
 // Base8 (alphabet: 01234567)
+#[cfg(feature = "rfc4648")]
 pub const BASE8: Encoding = new_encoding! {
     symbols: "01234567",
 };
@@ -15,12 +19,15 @@ pub const BASE8: Encoding = new_encoding! {
 pub const BASE10: &str = "0123456789";
 
 // Base16 lower hexadecimal (alphabet: 0123456789abcdef)
+#[cfg(feature = "rfc4648")]
 pub const BASE16_LOWER: Encoding = data_encoding::HEXLOWER_PERMISSIVE;
 
 // Base16 upper hexadecimal (alphabet: 0123456789ABCDEF).
+#[cfg(feature = "rfc4648")]
 pub const BASE16_UPPER: Encoding = data_encoding::HEXUPPER_PERMISSIVE;
 
 // Base32, rfc4648 no padding (alphabet: abcdefghijklmnopqrstuvwxyz234567).
+#[cfg(feature = "rfc4648")]
 pub const BASE32_NOPAD_LOWER: Encoding = new_encoding! {
     symbols: "abcdefghijklmnopqrstuvwxyz234567",
     translate_from: "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
@@ -28,6 +35,7 @@ pub const BASE32_NOPAD_LOWER: Encoding = new_encoding! {
 };
 
 // Base32, rfc4648 no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZ234567).
+#[cfg(feature = "rfc4648")]
 pub const BASE32_NOPAD_UPPER: Encoding = new_encoding! {
     symbols: "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
     translate_from: "abcdefghijklmnopqrstuvwxyz",
@@ -35,6 +43,7 @@ pub const BASE32_NOPAD_UPPER: Encoding = new_encoding! {
 };
 
 // Base32, rfc4648 with padding (alphabet: abcdefghijklmnopqrstuvwxyz234567).
+#[cfg(feature = "rfc4648")]
 pub const BASE32_PAD_LOWER: Encoding = new_encoding! {
     symbols: "abcdefghijklmnopqrstuvwxyz234567",
     translate_from: "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
@@ -43,6 +52,7 @@ pub const BASE32_PAD_LOWER: Encoding = new_encoding! {
 };
 
 // Base32, rfc4648 with padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZ234567).
+#[cfg(feature = "rfc4648")]
 pub const BASE32_PAD_UPPER: Encoding = new_encoding! {
     symbols: "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
     translate_from: "abcdefghijklmnopqrstuvwxyz",
@@ -51,6 +61,7 @@ pub const BASE32_PAD_UPPER: Encoding = new_encoding! {
 };
 
 // Base32hex, rfc4648 no padding (alphabet: 0123456789abcdefghijklmnopqrstuv).
+#[cfg(feature = "rfc4648")]
 pub const BASE32HEX_NOPAD_LOWER: Encoding = new_encoding! {
     symbols: "0123456789abcdefghijklmnopqrstuv",
     translate_from: "ABCDEFGHIJKLMNOPQRSTUV",
@@ -58,6 +69,7 @@ pub const BASE32HEX_NOPAD_LOWER: Encoding = new_encoding! {
 };
 
 // Base32hex, rfc4648 no padding (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUV).
+#[cfg(feature = "rfc4648")]
 pub const BASE32HEX_NOPAD_UPPER: Encoding = new_encoding! {
     symbols: "0123456789ABCDEFGHIJKLMNOPQRSTUV",
     translate_from: "abcdefghijklmnopqrstuv",
@@ -65,6 +77,7 @@ pub const BASE32HEX_NOPAD_UPPER: Encoding = new_encoding! {
 };
 
 // Base32hex, rfc4648 with padding (alphabet: 0123456789abcdefghijklmnopqrstuv).
+#[cfg(feature = "rfc4648")]
 pub const BASE32HEX_PAD_LOWER: Encoding = new_encoding! {
     symbols: "0123456789abcdefghijklmnopqrstuv",
     translate_from: "ABCDEFGHIJKLMNOPQRSTUV",
@@ -73,6 +86,7 @@ pub const BASE32HEX_PAD_LOWER: Encoding = new_encoding! {
 };
 
 /// Base32hex, rfc4648 with padding (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUV).
+#[cfg(feature = "rfc4648")]
 pub const BASE32HEX_PAD_UPPER: Encoding = new_encoding! {
     symbols: "0123456789ABCDEFGHIJKLMNOPQRSTUV",
     translate_from: "abcdefghijklmnopqrstuv",
@@ -81,6 +95,7 @@ pub const BASE32HEX_PAD_UPPER: Encoding = new_encoding! {
 };
 
 // z-base-32 (used by Tahoe-LAFS) (alphabet: ybndrfg8ejkmcpqxot1uwisza345h769).
+#[cfg(feature = "rfc4648")]
 pub const BASE32Z: Encoding = new_encoding! {
     symbols: "ybndrfg8ejkmcpqxot1uwisza345h769",
 };
@@ -91,6 +106,9 @@ pub const BASE36_LOWER: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
 /// Base36, [0-9A-Z] no padding (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ).
 pub const BASE36_UPPER: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
+/// Base45, rfc9285 (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:).
+pub const BASE45: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
 // Base58 Flickr's alphabet for creating short urls from photo ids.
 pub const BASE58_FLICKR: &str = "123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ";
 
@@ -98,13 +116,63 @@ pub const BASE58_FLICKR: &str = "123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMN
 pub const BASE58_BITCOIN: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
 
 // Base64, rfc4648 no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/).
+#[cfg(feature = "rfc4648")]
 pub const BASE64_NOPAD: Encoding = data_encoding::BASE64_NOPAD;
 
 // Base64, rfc4648 with padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/).
+#[cfg(feature = "rfc4648")]
 pub const BASE64_PAD: Encoding = data_encoding::BASE64;
 
 // Base64 url, rfc4648 no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_).
+#[cfg(feature = "rfc4648")]
 pub const BASE64URL_NOPAD: Encoding = data_encoding::BASE64URL_NOPAD;
 
 // Base64 url, rfc4648 with padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_).
+#[cfg(feature = "rfc4648")]
 pub const BASE64URL_PAD: Encoding = data_encoding::BASE64URL;
+
+/// Proquint consonants, 4 bits each.
+pub const PROQUINT_CONSONANTS: &str = "bdfghjklmnprstvz";
+
+/// Proquint vowels, 2 bits each.
+pub const PROQUINT_VOWELS: &str = "aiou";
+
+/// Proquint, the payload starts with the rest of the "pro-" magic.
+pub const PROQUINT_PREFIX: &str = "ro-";
+
+/// Values of the proquint consonants, see [`symbol_table`].
+pub(crate) const PROQUINT_CONSONANT_TABLE: [u8; 256] =
+    symbol_table(Some(PROQUINT_CONSONANTS), false);
+
+/// Values of the proquint vowels, see [`symbol_table`].
+pub(crate) const PROQUINT_VOWEL_TABLE: [u8; 256] = symbol_table(Some(PROQUINT_VOWELS), false);
+
+/// Base256Emoji, one emoji per byte.
+pub const BASE256_EMOJI: &str = "🚀🪐☄🛰🌌🌑🌒🌓🌔🌕🌖🌗🌘🌍🌏🌎🐉☀💻🖥💾💿😂❤😍🤣😊🙏💕😭😘👍😅👏😁🔥🥰💔💖💙😢🤔😆🙄💪😉☺👌🤗💜😔😎😇🌹🤦🎉💞✌✨🤷😱😌🌸🙌😋💗💚😏💛🙂💓🤩😄😀🖤😃💯🙈👇🎶😒🤭❣😜💋👀😪😑💥🙋😞😩😡🤪👊🥳😥🤤👉💃😳✋😚😝😴🌟😬🙃🍀🌷😻😓⭐✅🥺🌈😈🤘💦✔😣🏃💐☹🎊💘😠☝😕🌺🎂🌻😐🖕💝🙊😹🗣💫💀👑🎵🤞😛🔴😤🌼😫⚽🤙☕🏆🤫👈😮🙆🍻🍃🐶💁😲🌿🧡🎁⚡🌞🎈❌✊👋😰🤨😶🤝🚶💰🍓💢🤟🙁🚨💨🤬✈🎀🍺🤓😙💟🌱😖👶🥴▶➡❓💎💸⬇😨🌚🦋😷🕺⚠🙅😟😵👎🤲🤠🤧📌🔵💅🧐🐾🍒😗🤑🌊🤯🐷☎💧😯💆👆🎤🙇🍑❄🌴💣🐸💌📍🥀🤢👅💡💩👐📸👻🤐🤮🎼🥵🚩🍎🍊👼💍📣🥂";
+
+/// The value of the bytes that are not a symbol in a [`symbol_table`].
+pub(crate) const NOT_A_SYMBOL: u8 = 0xff;
+
+/// Build the table of the value of every byte that is a symbol of `alphabet`, and
+/// [`NOT_A_SYMBOL`] for the other bytes. Letters match regardless of their case if
+/// `case_insensitive` is set.
+///
+/// Alphabets of non-ASCII symbols, and bases without an alphabet, have no symbol bytes.
+pub(crate) const fn symbol_table(alphabet: Option<&str>, case_insensitive: bool) -> [u8; 256] {
+    let mut table = [NOT_A_SYMBOL; 256];
+    let symbols = match alphabet {
+        Some(alphabet) if alphabet.is_ascii() => alphabet.as_bytes(),
+        _ => return table,
+    };
+    let mut value = 0;
+    while value < symbols.len() {
+        let symbol = symbols[value];
+        table[symbol as usize] = value as u8;
+        if case_insensitive {
+            table[symbol.to_ascii_lowercase() as usize] = value as u8;
+            table[symbol.to_ascii_uppercase() as usize] = value as u8;
+        }
+        value += 1;
+    }
+    table
+}
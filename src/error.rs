@@ -1,49 +1,267 @@
 use core::fmt;
 
+use crate::array::ArrayString;
+use crate::base::{Base, Status};
+
 /// Type alias to use this library's [`Error`] type in a `Result`.
 pub type Result<T> = core::result::Result<T, Error>;
 
 /// Error types
+///
+/// Positions are byte offsets into the decoded string. Errors returned by the top-level
+/// [`decode`](crate::decode) count the base code as well, errors returned by
+/// [`Base::decode`] only count the encoded data.
 #[derive(PartialEq, Eq, Clone, Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Unknown base code.
     UnknownBase(char),
+    /// The base code is reserved by the multibase spec, e.g. `Q` and `1` for legacy base58btc
+    /// strings without a code.
+    ReservedPrefix(char),
+    /// Unknown base name, truncated to its first 32 bytes.
+    UnknownName(ArrayString<32>),
     /// Invalid string.
     InvalidBaseString,
+    /// The input is empty, there is no base code.
+    EmptyInput,
+    /// A symbol is not part of the alphabet of the base.
+    InvalidSymbol {
+        /// The base the input is decoded with.
+        base: Base,
+        /// The position of the symbol.
+        position: usize,
+        /// The invalid symbol.
+        char: char,
+    },
+    /// The length of the encoded data is invalid for the base.
+    InvalidLength {
+        /// The base the input is decoded with.
+        base: Base,
+        /// The length in bytes of the encoded data.
+        len: usize,
+    },
+    /// The padding is invalid.
+    InvalidPadding {
+        /// The base the input is decoded with.
+        base: Base,
+        /// The position of the first padding symbol of the invalid padding.
+        position: usize,
+    },
+    /// The unused bits of the last symbol are not zero.
+    TrailingBits {
+        /// The base the input is decoded with.
+        base: Base,
+        /// The position of the symbol with non-zero trailing bits.
+        position: usize,
+    },
+    /// The input decodes but is not the canonical encoding of its bytes.
+    NonCanonical {
+        /// The base the input is decoded with.
+        base: Base,
+        /// The position of the first symbol differing from the canonical encoding.
+        position: usize,
+    },
+    /// The identity encoded data is not valid UTF-8, hence not a string.
+    InvalidUtf8 {
+        /// The position of the first invalid byte.
+        position: usize,
+    },
+    /// The base code is already used by a built-in base or a registered codec.
+    CodeInUse(char),
+    /// The output buffer is too small for the result.
+    BufferTooSmall,
+    /// The base is not block-aligned and cannot be encoded or decoded as a stream.
+    NotStreamable(Base),
+    /// The status of the base in the multibase spec is below the required one.
+    RejectedStatus {
+        /// The base of the input.
+        base: Base,
+        /// The status of the base.
+        status: Status,
+    },
+    /// The input is longer than the limit, see [`DecodeLimits`](crate::DecodeLimits).
+    InputTooLong {
+        /// The length in bytes of the input.
+        len: usize,
+        /// The maximum length in bytes.
+        max: usize,
+    },
+    /// The input may decode to more bytes than the limit, see
+    /// [`DecodeLimits`](crate::DecodeLimits).
+    OutputTooLong {
+        /// The maximum length in bytes of the decoded data.
+        len: usize,
+        /// The maximum length in bytes allowed.
+        max: usize,
+    },
+    /// The base does not support the operation, or its cargo feature is disabled.
+    Unsupported(Base),
+    /// The input is valid but not in the base required by the type.
+    WrongBase {
+        /// The required base.
+        expected: Base,
+        /// The base of the input.
+        found: Base,
+    },
 }
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+/// A decoding error of the `const fn` decoders shared with the
+/// [`multibase!`](crate::multibase!) literals, see [`Fault::into_error`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(crate) enum Fault {
+    /// Invalid symbol at the byte position.
+    Symbol(usize),
+    /// Invalid length of the input.
+    Length,
+    /// The output buffer is too small.
+    BufferTooSmall,
+}
+
+impl Fault {
+    /// Convert the fault raised while decoding `input` with `base`.
+    pub(crate) fn into_error(self, base: Base, input: &str) -> Error {
         match self {
-            Error::UnknownBase(code) => write!(f, "Unknown base code: {}", code),
-            Error::InvalidBaseString => write!(f, "Invalid base string"),
+            Self::Symbol(position) => {
+                // Report the whole symbol if the position is within a multi-byte char.
+                let position = (0..=position)
+                    .rev()
+                    .find(|&position| input.is_char_boundary(position))
+                    .unwrap_or_default();
+                Error::InvalidSymbol {
+                    base,
+                    position,
+                    char: input[position..].chars().next().unwrap_or_default(),
+                }
+            }
+            Self::Length => Error::InvalidLength {
+                base,
+                len: input.len(),
+            },
+            Self::BufferTooSmall => Error::BufferTooSmall,
         }
     }
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for Error {}
-
-impl From<base_x::DecodeError> for Error {
-    fn from(_: base_x::DecodeError) -> Self {
-        Self::InvalidBaseString
+impl Error {
+    /// Convert a `data_encoding` error raised while decoding `input` with `base`.
+    #[cfg(feature = "rfc4648")]
+    pub(crate) fn from_data_encoding(
+        base: Base,
+        input: &[u8],
+        err: data_encoding::DecodeError,
+    ) -> Self {
+        let position = err.position;
+        match err.kind {
+            data_encoding::DecodeKind::Length => Self::InvalidLength {
+                base,
+                len: input.len(),
+            },
+            data_encoding::DecodeKind::Symbol => Self::InvalidSymbol {
+                base,
+                position,
+                char: char_at(input, position),
+            },
+            data_encoding::DecodeKind::Trailing => Self::TrailingBits { base, position },
+            data_encoding::DecodeKind::Padding => Self::InvalidPadding { base, position },
+        }
     }
-}
 
-impl From<base256emoji::DecodeError> for Error {
-    fn from(_: base256emoji::DecodeError) -> Self {
-        Self::InvalidBaseString
+    /// Move the positions of the error by `offset` bytes.
+    pub(crate) fn offset(self, offset: usize) -> Self {
+        match self {
+            Self::InvalidSymbol {
+                base,
+                position,
+                char,
+            } => Self::InvalidSymbol {
+                base,
+                position: position + offset,
+                char,
+            },
+            Self::InvalidPadding { base, position } => Self::InvalidPadding {
+                base,
+                position: position + offset,
+            },
+            Self::TrailingBits { base, position } => Self::TrailingBits {
+                base,
+                position: position + offset,
+            },
+            Self::NonCanonical { base, position } => Self::NonCanonical {
+                base,
+                position: position + offset,
+            },
+            err => err,
+        }
     }
 }
 
-impl From<data_encoding::DecodeError> for Error {
-    fn from(_: data_encoding::DecodeError) -> Self {
-        Self::InvalidBaseString
-    }
+/// Get the char starting at byte `position` of `input`, which may not be valid UTF-8.
+#[cfg(feature = "rfc4648")]
+fn char_at(input: &[u8], position: usize) -> char {
+    let bytes = &input[position..input.len().min(position + 4)];
+    let valid = match core::str::from_utf8(bytes) {
+        Ok(valid) => valid,
+        Err(err) => core::str::from_utf8(&bytes[..err.valid_up_to()]).unwrap_or_default(),
+    };
+    valid.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER)
 }
 
-impl From<base45::DecodeError> for Error {
-    fn from(_: base45::DecodeError) -> Self {
-        Self::InvalidBaseString
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownBase(code) => write!(f, "Unknown base code: {}", code),
+            Error::ReservedPrefix(code) => write!(f, "Reserved base code: {}", code),
+            Error::UnknownName(name) => write!(f, "Unknown base name: {}", name),
+            Error::InvalidBaseString => write!(f, "Invalid base string"),
+            Error::EmptyInput => write!(f, "Empty input"),
+            Error::InvalidSymbol {
+                base,
+                position,
+                char,
+            } => write!(
+                f,
+                "Invalid {:?} symbol {:?} at position {}",
+                base, char, position
+            ),
+            Error::InvalidLength { base, len } => write!(f, "Invalid {:?} length: {}", base, len),
+            Error::InvalidPadding { base, position } => {
+                write!(f, "Invalid {:?} padding at position {}", base, position)
+            }
+            Error::TrailingBits { base, position } => write!(
+                f,
+                "Non-zero {:?} trailing bits at position {}",
+                base, position
+            ),
+            Error::NonCanonical { base, position } => write!(
+                f,
+                "Non-canonical {:?} encoding at position {}",
+                base, position
+            ),
+            Error::InvalidUtf8 { position } => {
+                write!(f, "Invalid UTF-8 at position {}", position)
+            }
+            Error::CodeInUse(code) => write!(f, "Base code already in use: {}", code),
+            Error::BufferTooSmall => write!(f, "Output buffer is too small"),
+            Error::NotStreamable(base) => write!(f, "Base cannot be streamed: {:?}", base),
+            Error::RejectedStatus { base, status } => {
+                write!(f, "Base {:?} is rejected, its status is {:?}", base, status)
+            }
+            Error::InputTooLong { len, max } => {
+                write!(f, "Input too long: {} bytes, the limit is {}", len, max)
+            }
+            Error::OutputTooLong { len, max } => write!(
+                f,
+                "Output may be too long: {} bytes, the limit is {}",
+                len, max
+            ),
+            Error::Unsupported(base) => write!(f, "Unsupported operation for base: {:?}", base),
+            Error::WrongBase { expected, found } => {
+                write!(f, "Expected base {:?}, found {:?}", expected, found)
+            }
+        }
     }
 }
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,67 @@
+//! Guess the base of a string without a multibase code.
+
+use core::cmp::Reverse;
+
+use crate::base::Base;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+/// How likely a guessed base is.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Confidence {
+    /// The string is valid in the base, but bases with smaller alphabets also fit.
+    Low,
+    /// Only the bases with the smallest alphabet fit better.
+    Medium,
+    /// No base with a smaller alphabet fits the string.
+    High,
+}
+
+/// Guess the base of a string without a multibase code, ranked from the most to the least
+/// likely.
+///
+/// A base is a candidate if the string is its canonical encoding of some bytes, which checks
+/// the alphabet, the case, the padding and the length. The candidates with the smallest
+/// alphabet are the most likely, ties are ranked by their status in the multibase spec.
+/// Identity is never guessed.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{guess, Base, Confidence};
+///
+/// let guesses = guess("deadbeef");
+/// assert_eq!(guesses[0], (Base::Base16Lower, Confidence::High));
+/// assert!(guesses[1..].iter().all(|&(_, confidence)| confidence < Confidence::High));
+///
+/// let guesses = guess("3yZe7d");
+/// assert_eq!(guesses[0], (Base::Base58Btc, Confidence::High));
+/// ```
+pub fn guess<T: AsRef<str>>(input: T) -> Vec<(Base, Confidence)> {
+    let input = input.as_ref();
+    if input.is_empty() {
+        return Vec::new();
+    }
+
+    let candidates: Vec<Base> = Base::iter()
+        .filter(|&base| base != Base::Identity && base.is_canonical(input))
+        .collect();
+    let mut radixes: Vec<u32> = candidates.iter().map(Base::radix).collect();
+    radixes.sort_unstable();
+    radixes.dedup();
+
+    let mut guesses: Vec<(Base, Confidence)> = candidates
+        .into_iter()
+        .map(|base| {
+            let confidence = match radixes.iter().position(|&radix| radix == base.radix()) {
+                Some(0) => Confidence::High,
+                Some(1) => Confidence::Medium,
+                _ => Confidence::Low,
+            };
+            (base, confidence)
+        })
+        .collect();
+    guesses.sort_by_key(|&(base, confidence)| (Reverse(confidence), Reverse(base.status())));
+    guesses
+}
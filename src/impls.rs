@@ -1,24 +1,145 @@
+use crate::base::{Base, Family};
 use crate::encoding;
-use crate::error::Result;
-use base256emoji::{Base, Emoji};
+use crate::error::{Error, Fault, Result};
+#[cfg(feature = "base-x")]
+use crate::radix;
+#[cfg(feature = "emoji")]
+use base256emoji::{Base as _, Emoji};
 
-#[cfg(not(feature = "std"))]
-use alloc::{string::String, vec::Vec};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec, vec::Vec};
+
+/// Implement the codec methods `$item` of `$type` if the cargo feature `$feature` is enabled,
+/// otherwise implement them by returning [`Error::Unsupported`].
+macro_rules! gate_codec {
+    ($feature:literal, $type:ident; $($item:item)*) => {
+        $( #[cfg(feature = $feature)] $item )*
+
+        // The lengths and the encoding are only reached through `Base`, which checks
+        // `Base::is_supported` first.
+        #[cfg(all(feature = "alloc", not(feature = $feature)))]
+        fn encode<I: AsRef<[u8]>>(_input: I) -> String {
+            unreachable!("unsupported base: {:?}", Base::$type)
+        }
+
+        #[cfg(all(feature = "alloc", not(feature = $feature)))]
+        fn decode<I: AsRef<str>>(_input: I) -> Result<Vec<u8>> {
+            Err(Error::Unsupported(Base::$type))
+        }
+
+        #[cfg(not(feature = $feature))]
+        fn encoded_len(_input_len: usize) -> usize {
+            unreachable!("unsupported base: {:?}", Base::$type)
+        }
+
+        #[cfg(not(feature = $feature))]
+        fn decoded_len_max(_encoded_len: usize) -> usize {
+            unreachable!("unsupported base: {:?}", Base::$type)
+        }
+
+        #[cfg(not(feature = $feature))]
+        fn validate(_input: &str) -> Result<()> {
+            Err(Error::Unsupported(Base::$type))
+        }
+
+        #[cfg(not(feature = $feature))]
+        fn encode_to_slice(_input: &[u8], _output: &mut [u8]) -> Result<usize> {
+            Err(Error::Unsupported(Base::$type))
+        }
+
+        #[cfg(not(feature = $feature))]
+        fn decode_to_slice(_input: &str, _output: &mut [u8]) -> Result<usize> {
+            Err(Error::Unsupported(Base::$type))
+        }
+    };
+}
 
 macro_rules! derive_base_encoding {
-    ( $(#[$doc:meta] $type:ident, $encoding:expr;)* ) => {
+    (
+        $(
+            #[$doc:meta] $type:ident, $encoding:expr, $alphabet:expr
+            $(, padding = $padding:literal)? $(, case_insensitive = $ci:literal)?;
+        )*
+    ) => {
         $(
             #[$doc]
             #[derive(PartialEq, Eq, Clone, Copy, Debug)]
-            pub(crate) struct $type;
+            pub struct $type;
 
             impl BaseCodec for $type {
-                fn encode<I: AsRef<[u8]>>(input: I) -> String {
-                    $encoding.encode(input.as_ref())
-                }
+                #[cfg(all(feature = "std", feature = "rfc4648"))]
+                const ENCODING: Option<data_encoding::Encoding> = Some($encoding);
+                const ALPHABET: Option<&'static str> = Some($alphabet);
+                const RADIX: u32 = $alphabet.len() as u32;
+                $( const PADDING: Option<char> = Some($padding); )?
+                $( const CASE_INSENSITIVE: bool = $ci; )?
+                const FAMILY: Family = Family::Rfc4648;
+                const BLOCK_SIZE: Option<(usize, usize)> = Some(block_size(Self::RADIX));
+
+                gate_codec! {
+                    "rfc4648", $type;
+
+                    #[cfg(feature = "alloc")]
+                    fn encode<I: AsRef<[u8]>>(input: I) -> String {
+                        $encoding.encode(input.as_ref())
+                    }
+
+                    #[cfg(feature = "alloc")]
+                    fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
+                        let input = input.as_ref().as_bytes();
+                        $encoding
+                            .decode(input)
+                            .map_err(|err| Error::from_data_encoding(Base::$type, input, err))
+                    }
+
+                    fn encoded_len(input_len: usize) -> usize {
+                        $encoding.encode_len(input_len)
+                    }
+
+                    fn decoded_len_max(encoded_len: usize) -> usize {
+                        let bit = $encoding.bit_width();
+                        encoded_len / 8 * bit + encoded_len % 8 * bit / 8
+                    }
 
-                fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
-                    Ok($encoding.decode(input.as_ref().as_bytes())?)
+                    fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize> {
+                        let len = $encoding.encode_len(input.len());
+                        let output = output.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+                        $encoding.encode_mut(input, output);
+                        Ok(len)
+                    }
+
+                    fn validate(input: &str) -> Result<()> {
+                        let input = input.as_bytes();
+                        $encoding
+                            .decode_len(input.len())
+                            .map_err(|err| Error::from_data_encoding(Base::$type, input, err))?;
+                        // Decode whole blocks at a time into a scratch buffer, 64 symbols are
+                        // always a multiple of the block size.
+                        let mut scratch = [0; 48];
+                        for (index, chunk) in input.chunks(64).enumerate() {
+                            let len = $encoding
+                                .decode_len(chunk.len())
+                                .map_err(|err| Error::from_data_encoding(Base::$type, input, err))?;
+                            $encoding
+                                .decode_mut(chunk, &mut scratch[..len])
+                                .map_err(|partial| {
+                                    Error::from_data_encoding(Base::$type, chunk, partial.error)
+                                        .offset(index * 64)
+                                })?;
+                        }
+                        Ok(())
+                    }
+
+                    fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize> {
+                        let input = input.as_bytes();
+                        let len = $encoding
+                            .decode_len(input.len())
+                            .map_err(|err| Error::from_data_encoding(Base::$type, input, err))?;
+                        let output = output.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+                        $encoding
+                            .decode_mut(input, output)
+                            .map_err(|partial| Error::from_data_encoding(Base::$type, input, partial.error))
+                    }
                 }
             }
         )*
@@ -30,15 +151,45 @@ macro_rules! derive_base_x {
         $(
             #[$doc]
             #[derive(PartialEq, Eq, Clone, Copy, Debug)]
-            pub(crate) struct $type;
+            pub struct $type;
 
             impl BaseCodec for $type {
-                fn encode<I: AsRef<[u8]>>(input: I) -> String {
-                    base_x::encode($encoding, input.as_ref())
-                }
+                const ALPHABET: Option<&'static str> = Some($encoding);
+                const RADIX: u32 = $encoding.len() as u32;
+                const FAMILY: Family = Family::BaseX;
+
+                gate_codec! {
+                    "base-x", $type;
+
+                    #[cfg(feature = "alloc")]
+                    fn encode<I: AsRef<[u8]>>(input: I) -> String {
+                        radix::encode($encoding.as_bytes(), input.as_ref())
+                    }
+
+                    #[cfg(feature = "alloc")]
+                    fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
+                        radix::decode(Base::$type, input.as_ref())
+                    }
+
+                    fn encoded_len(input_len: usize) -> usize {
+                        radix::encoded_len($encoding.len(), input_len)
+                    }
+
+                    fn decoded_len_max(encoded_len: usize) -> usize {
+                        radix::decoded_len_max(encoded_len)
+                    }
+
+                    fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize> {
+                        radix::encode_to_slice($encoding.as_bytes(), input, output)
+                    }
 
-                fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
-                    Ok(base_x::decode($encoding, input.as_ref())?)
+                    fn validate(input: &str) -> Result<()> {
+                        radix::validate(Base::$type, input)
+                    }
+
+                    fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize> {
+                        radix::decode_to_slice(Base::$type, input, output)
+                    }
                 }
             }
         )*
@@ -46,76 +197,241 @@ macro_rules! derive_base_x {
 }
 
 pub(crate) trait BaseCodec {
+    /// The RFC 4648 style encoding backing this base, if it is block-aligned.
+    #[cfg(all(feature = "std", feature = "rfc4648"))]
+    const ENCODING: Option<data_encoding::Encoding> = None;
+
+    /// The symbols of the alphabet in order of their value, if the base has an alphabet.
+    const ALPHABET: Option<&'static str>;
+
+    /// The number of distinct symbol values.
+    const RADIX: u32;
+
+    /// The padding symbol, if the base is padded.
+    const PADDING: Option<char> = None;
+
+    /// Whether letters are decoded regardless of their case.
+    const CASE_INSENSITIVE: bool = false;
+
+    /// The family of encodings of this base.
+    const FAMILY: Family;
+
+    /// The number of bytes and symbols of a block, if the base is block-aligned.
+    const BLOCK_SIZE: Option<(usize, usize)> = None;
+
+    /// The value of every byte that is a symbol of the alphabet.
+    const SYMBOL_TABLE: [u8; 256] = encoding::symbol_table(Self::ALPHABET, Self::CASE_INSENSITIVE);
+
     /// Encode with the given byte slice.
+    #[cfg(feature = "alloc")]
     fn encode<I: AsRef<[u8]>>(input: I) -> String;
 
     /// Decode with the given string.
+    #[cfg(feature = "alloc")]
     fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>>;
+
+    /// Get the encoded length in bytes of `input_len` bytes, or an upper bound of it.
+    fn encoded_len(input_len: usize) -> usize;
+
+    /// Get an upper bound of the decoded length of `encoded_len` bytes of input.
+    fn decoded_len_max(encoded_len: usize) -> usize;
+
+    /// Check that the given string only contains symbols of the alphabet, and has a valid
+    /// length where the base constrains it.
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    fn validate(input: &str) -> Result<()>;
+
+    /// Encode with the given byte slice into `output`, returns the number of bytes written.
+    fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize>;
+
+    /// Decode with the given string into `output`, returns the number of bytes written.
+    fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize>;
+}
+
+/// Get the number of bytes and symbols of a block of a base with a power of two `radix`.
+const fn block_size(radix: u32) -> (usize, usize) {
+    let bit = radix.trailing_zeros() as usize;
+    let mut bytes = 1;
+    while bytes * 8 % bit != 0 {
+        bytes += 1;
+    }
+    (bytes, bytes * 8 / bit)
+}
+
+fn copy_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize> {
+    output
+        .get_mut(..input.len())
+        .ok_or(Error::BufferTooSmall)?
+        .copy_from_slice(input);
+    Ok(input.len())
 }
 
 /// Identity, 8-bit binary (encoder and decoder keeps data unmodified).
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
-pub(crate) struct Identity;
+pub struct Identity;
 
 impl BaseCodec for Identity {
+    const ALPHABET: Option<&'static str> = None;
+    const RADIX: u32 = 256;
+    const FAMILY: Family = Family::Identity;
+    const BLOCK_SIZE: Option<(usize, usize)> = Some((1, 1));
+
+    #[cfg(feature = "alloc")]
     fn encode<I: AsRef<[u8]>>(input: I) -> String {
         String::from_utf8(input.as_ref().to_vec()).expect("input must be valid UTF-8 bytes")
     }
 
+    #[cfg(feature = "alloc")]
     fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
         Ok(input.as_ref().as_bytes().to_vec())
     }
+
+    fn encoded_len(input_len: usize) -> usize {
+        input_len
+    }
+
+    fn decoded_len_max(encoded_len: usize) -> usize {
+        encoded_len
+    }
+
+    fn validate(_input: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize> {
+        copy_to_slice(input, output)
+    }
+
+    fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize> {
+        copy_to_slice(input.as_bytes(), output)
+    }
 }
 
 /// Base256Emoji (alphabet: 🚀🪐☄🛰🌌🌑🌒🌓🌔🌕🌖🌗🌘🌍🌏🌎🐉☀💻🖥💾💿😂❤😍🤣😊🙏💕😭😘👍😅👏😁🔥🥰💔💖💙😢🤔😆🙄💪😉☺👌🤗💜😔😎😇🌹🤦🎉💞✌✨🤷😱😌🌸🙌😋💗💚😏💛🙂💓🤩😄😀🖤😃💯🙈👇🎶😒🤭❣😜💋👀😪😑💥🙋😞😩😡🤪👊🥳😥🤤👉💃😳✋😚😝😴🌟😬🙃🍀🌷😻😓⭐✅🥺🌈😈🤘💦✔😣🏃💐☹🎊💘😠☝😕🌺🎂🌻😐🖕💝🙊😹🗣💫💀👑🎵🤞😛🔴😤🌼😫⚽🤙☕🏆🤫👈😮🙆🍻🍃🐶💁😲🌿🧡🎁⚡🌞🎈❌✊👋😰🤨😶🤝🚶💰🍓💢🤟🙁🚨💨🤬✈🎀🍺🤓😙💟🌱😖👶🥴▶➡❓💎💸⬇😨🌚🦋😷🕺⚠🙅😟😵👎🤲🤠🤧📌🔵💅🧐🐾🍒😗🤑🌊🤯🐷☎💧😯💆👆🎤🙇🍑❄🌴💣🐸💌📍🥀🤢👅💡💩👐📸👻🤐🤮🎼🥵🚩🍎🍊👼💍📣🥂)
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
-pub(crate) struct Base256Emoji;
+pub struct Base256Emoji;
 
 impl BaseCodec for Base256Emoji {
-    fn encode<I: AsRef<[u8]>>(input: I) -> String {
-        Emoji::encode(input.as_ref())
-    }
+    const ALPHABET: Option<&'static str> = Some(encoding::BASE256_EMOJI);
+    const RADIX: u32 = 256;
+    const FAMILY: Family = Family::Emoji;
 
-    fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
-        Emoji::decode(input.as_ref()).map_err(|e| e.into())
+    gate_codec! {
+        "emoji", Base256Emoji;
+
+        #[cfg(feature = "alloc")]
+        fn encode<I: AsRef<[u8]>>(input: I) -> String {
+            Emoji::encode(input.as_ref())
+        }
+
+        #[cfg(feature = "alloc")]
+        fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
+            input
+                .as_ref()
+                .char_indices()
+                .map(|(position, char)| emoji_index(position, char))
+                .collect()
+        }
+
+        fn encoded_len(input_len: usize) -> usize {
+            // Every emoji of the alphabet is 3 or 4 bytes long in UTF-8.
+            input_len * 4
+        }
+
+        fn decoded_len_max(encoded_len: usize) -> usize {
+            encoded_len / 3
+        }
+
+        fn validate(input: &str) -> Result<()> {
+            for (position, char) in input.char_indices() {
+                emoji_index(position, char)?;
+            }
+            Ok(())
+        }
+
+        fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize> {
+            let mut len = 0;
+            for &byte in input {
+                let emoji = Emoji::ALPHABET[usize::from(byte)];
+                let end = len + emoji.len_utf8();
+                emoji.encode_utf8(output.get_mut(len..end).ok_or(Error::BufferTooSmall)?);
+                len = end;
+            }
+            Ok(len)
+        }
+
+        fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize> {
+            let mut len = 0;
+            for (position, char) in input.char_indices() {
+                *output.get_mut(len).ok_or(Error::BufferTooSmall)? = emoji_index(position, char)?;
+                len += 1;
+            }
+            Ok(len)
+        }
     }
 }
 
+#[cfg(feature = "emoji")]
+fn emoji_index(position: usize, char: char) -> Result<u8> {
+    Emoji::get_index(char).ok_or(Error::InvalidSymbol {
+        base: Base::Base256Emoji,
+        position,
+        char,
+    })
+}
+
 derive_base_encoding! {
     /// Base2 (alphabet: 01).
-    Base2, encoding::BASE2;
+    Base2, encoding::BASE2,
+        "01";
     /// Base8 (alphabet: 01234567).
-    Base8, encoding::BASE8;
+    Base8, encoding::BASE8,
+        "01234567";
     /// Base16 lower hexadecimal (alphabet: 0123456789abcdef).
-    Base16Lower, encoding::BASE16_LOWER;
+    Base16Lower, encoding::BASE16_LOWER,
+        "0123456789abcdef", case_insensitive = true;
     /// Base16 upper hexadecimal (alphabet: 0123456789ABCDEF).
-    Base16Upper, encoding::BASE16_UPPER;
+    Base16Upper, encoding::BASE16_UPPER,
+        "0123456789ABCDEF", case_insensitive = true;
     /// Base32, rfc4648 no padding (alphabet: abcdefghijklmnopqrstuvwxyz234567).
-    Base32Lower, encoding::BASE32_NOPAD_LOWER;
+    Base32Lower, encoding::BASE32_NOPAD_LOWER,
+        "abcdefghijklmnopqrstuvwxyz234567", case_insensitive = true;
     /// Base32, rfc4648 no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZ234567).
-    Base32Upper, encoding::BASE32_NOPAD_UPPER;
+    Base32Upper, encoding::BASE32_NOPAD_UPPER,
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567", case_insensitive = true;
     /// Base32, rfc4648 with padding (alphabet: abcdefghijklmnopqrstuvwxyz234567).
-    Base32PadLower, encoding::BASE32_PAD_LOWER;
+    Base32PadLower, encoding::BASE32_PAD_LOWER,
+        "abcdefghijklmnopqrstuvwxyz234567", padding = '=', case_insensitive = true;
     /// Base32, rfc4648 with padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZ234567).
-    Base32PadUpper, encoding::BASE32_PAD_UPPER;
+    Base32PadUpper, encoding::BASE32_PAD_UPPER,
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567", padding = '=', case_insensitive = true;
     /// Base32hex, rfc4648 no padding (alphabet: 0123456789abcdefghijklmnopqrstuv).
-    Base32HexLower, encoding::BASE32HEX_NOPAD_LOWER;
+    Base32HexLower, encoding::BASE32HEX_NOPAD_LOWER,
+        "0123456789abcdefghijklmnopqrstuv", case_insensitive = true;
     /// Base32hex, rfc4648 no padding (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUV).
-    Base32HexUpper, encoding::BASE32HEX_NOPAD_UPPER;
+    Base32HexUpper, encoding::BASE32HEX_NOPAD_UPPER,
+        "0123456789ABCDEFGHIJKLMNOPQRSTUV", case_insensitive = true;
     /// Base32hex, rfc4648 with padding (alphabet: 0123456789abcdefghijklmnopqrstuv).
-    Base32HexPadLower, encoding::BASE32HEX_PAD_LOWER;
+    Base32HexPadLower, encoding::BASE32HEX_PAD_LOWER,
+        "0123456789abcdefghijklmnopqrstuv", padding = '=', case_insensitive = true;
     /// Base32hex, rfc4648 with padding (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUV).
-    Base32HexPadUpper, encoding::BASE32HEX_PAD_UPPER;
+    Base32HexPadUpper, encoding::BASE32HEX_PAD_UPPER,
+        "0123456789ABCDEFGHIJKLMNOPQRSTUV", padding = '=', case_insensitive = true;
     /// z-base-32 (used by Tahoe-LAFS) (alphabet: ybndrfg8ejkmcpqxot1uwisza345h769).
-    Base32Z, encoding::BASE32Z;
+    Base32Z, encoding::BASE32Z,
+        "ybndrfg8ejkmcpqxot1uwisza345h769";
     /// Base64, rfc4648 no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/).
-    Base64, encoding::BASE64_NOPAD;
+    Base64, encoding::BASE64_NOPAD,
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
     /// Base64, rfc4648 with padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/).
-    Base64Pad, encoding::BASE64_PAD;
+    Base64Pad, encoding::BASE64_PAD,
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/", padding = '=';
     /// Base64 url, rfc4648 no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_).
-    Base64Url, encoding::BASE64URL_NOPAD;
+    Base64Url, encoding::BASE64URL_NOPAD,
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
     /// Base64 url, rfc4648 with padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_).
-    Base64UrlPad, encoding::BASE64URL_PAD;
+    Base64UrlPad, encoding::BASE64URL_PAD,
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_", padding = '=';
 }
 
 derive_base_x! {
@@ -129,46 +445,413 @@ derive_base_x! {
 
 /// Base36, [0-9a-z] no padding (alphabet: abcdefghijklmnopqrstuvwxyz0123456789).
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
-pub(crate) struct Base36Lower;
+pub struct Base36Lower;
 
 impl BaseCodec for Base36Lower {
-    fn encode<I: AsRef<[u8]>>(input: I) -> String {
-        base_x::encode(encoding::BASE36_LOWER, input.as_ref())
-    }
+    const ALPHABET: Option<&'static str> = Some(encoding::BASE36_LOWER);
+    const RADIX: u32 = 36;
+    const CASE_INSENSITIVE: bool = true;
+    const FAMILY: Family = Family::BaseX;
 
-    fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
-        // The input is case insensitive, hence lowercase it
-        let lowercased = input.as_ref().to_ascii_lowercase();
-        Ok(base_x::decode(encoding::BASE36_LOWER, &lowercased)?)
+    gate_codec! {
+        "base-x", Base36Lower;
+
+        #[cfg(feature = "alloc")]
+        fn encode<I: AsRef<[u8]>>(input: I) -> String {
+            radix::encode(encoding::BASE36_LOWER.as_bytes(), input.as_ref())
+        }
+
+        #[cfg(feature = "alloc")]
+        fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
+            // The input is case insensitive
+            radix::decode(Base::Base36Lower, input.as_ref())
+        }
+
+        fn encoded_len(input_len: usize) -> usize {
+            radix::encoded_len(encoding::BASE36_LOWER.len(), input_len)
+        }
+
+        fn decoded_len_max(encoded_len: usize) -> usize {
+            radix::decoded_len_max(encoded_len)
+        }
+
+        fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize> {
+            radix::encode_to_slice(encoding::BASE36_LOWER.as_bytes(), input, output)
+        }
+
+        fn validate(input: &str) -> Result<()> {
+            radix::validate(Base::Base36Lower, input)
+        }
+
+        fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize> {
+            radix::decode_to_slice(Base::Base36Lower, input, output)
+        }
     }
 }
 
 /// Base36, [0-9A-Z] no padding (alphabet: ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789).
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
-pub(crate) struct Base36Upper;
+pub struct Base36Upper;
 
 impl BaseCodec for Base36Upper {
-    fn encode<I: AsRef<[u8]>>(input: I) -> String {
-        base_x::encode(encoding::BASE36_UPPER, input.as_ref())
-    }
+    const ALPHABET: Option<&'static str> = Some(encoding::BASE36_UPPER);
+    const RADIX: u32 = 36;
+    const CASE_INSENSITIVE: bool = true;
+    const FAMILY: Family = Family::BaseX;
 
-    fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
-        // The input is case insensitive, hence uppercase it
-        let uppercased = input.as_ref().to_ascii_uppercase();
-        Ok(base_x::decode(encoding::BASE36_UPPER, &uppercased)?)
+    gate_codec! {
+        "base-x", Base36Upper;
+
+        #[cfg(feature = "alloc")]
+        fn encode<I: AsRef<[u8]>>(input: I) -> String {
+            radix::encode(encoding::BASE36_UPPER.as_bytes(), input.as_ref())
+        }
+
+        #[cfg(feature = "alloc")]
+        fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
+            // The input is case insensitive
+            radix::decode(Base::Base36Upper, input.as_ref())
+        }
+
+        fn encoded_len(input_len: usize) -> usize {
+            radix::encoded_len(encoding::BASE36_UPPER.len(), input_len)
+        }
+
+        fn decoded_len_max(encoded_len: usize) -> usize {
+            radix::decoded_len_max(encoded_len)
+        }
+
+        fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize> {
+            radix::encode_to_slice(encoding::BASE36_UPPER.as_bytes(), input, output)
+        }
+
+        fn validate(input: &str) -> Result<()> {
+            radix::validate(Base::Base36Upper, input)
+        }
+
+        fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize> {
+            radix::decode_to_slice(Base::Base36Upper, input, output)
+        }
     }
 }
 
 /// Base45, rfc9285 (alphabet: 0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:).
-pub(crate) struct Base45;
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Base45;
 
 impl BaseCodec for Base45 {
+    const ALPHABET: Option<&'static str> = Some(encoding::BASE45);
+    const RADIX: u32 = 45;
+    const CASE_INSENSITIVE: bool = true;
+    const FAMILY: Family = Family::Base45;
+    const BLOCK_SIZE: Option<(usize, usize)> = Some((2, 3));
+
+    gate_codec! {
+        "base45", Base45;
+
+        #[cfg(feature = "alloc")]
+        fn encode<I: AsRef<[u8]>>(input: I) -> String {
+            let input = input.as_ref();
+            let mut output = vec![0; Self::encoded_len(input.len())];
+            Self::encode_to_slice(input, &mut output).expect("output is sized by encoded_len");
+            String::from_utf8(output).expect("the alphabet is ASCII")
+        }
+
+        #[cfg(feature = "alloc")]
+        fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
+            let input = input.as_ref();
+            let mut output = vec![0; Self::decoded_len_max(input.len())];
+            let len = Self::decode_to_slice(input, &mut output)?;
+            output.truncate(len);
+            Ok(output)
+        }
+
+        fn encoded_len(input_len: usize) -> usize {
+            input_len / 2 * 3 + input_len % 2 * 2
+        }
+
+        fn decoded_len_max(encoded_len: usize) -> usize {
+            encoded_len.div_ceil(3) * 2
+        }
+
+        fn validate(input: &str) -> Result<()> {
+            base45_check(input.as_bytes()).map_err(|fault| fault.into_error(Base::Base45, input))
+        }
+
+        fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize> {
+            let len = Self::encoded_len(input.len());
+            let output = output.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+            let alphabet = encoding::BASE45.as_bytes();
+            // Every chunk of 2 bytes is a number of 3 symbols, a trailing byte of 2 symbols, least
+            // significant first.
+            for (chunk, symbols) in input.chunks(2).zip(output.chunks_mut(3)) {
+                let mut value = chunk
+                    .iter()
+                    .fold(0, |value, &byte| value << 8 | usize::from(byte));
+                for symbol in symbols {
+                    *symbol = alphabet[value % 45];
+                    value /= 45;
+                }
+            }
+            Ok(len)
+        }
+
+        fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize> {
+            base45_decode(input.as_bytes(), output)
+                .map_err(|fault| fault.into_error(Base::Base45, input))
+        }
+    }
+}
+
+/// Check that `input` only contains Base45 symbols and has a valid length.
+#[cfg(feature = "base45")]
+const fn base45_check(input: &[u8]) -> core::result::Result<(), Fault> {
+    let mut position = 0;
+    while position < input.len() {
+        if Base45::SYMBOL_TABLE[input[position] as usize] == encoding::NOT_A_SYMBOL {
+            return Err(Fault::Symbol(position));
+        }
+        position += 1;
+    }
+    if input.len() % 3 == 1 {
+        return Err(Fault::Length);
+    }
+    Ok(())
+}
+
+/// Decode the Base45 `input` into `output`, returns the number of bytes written.
+///
+/// Shared with the [`multibase!`](crate::multibase!) literals, hence a `const fn`.
+#[cfg(feature = "base45")]
+pub(crate) const fn base45_decode(
+    input: &[u8],
+    output: &mut [u8],
+) -> core::result::Result<usize, Fault> {
+    if let Err(fault) = base45_check(input) {
+        return Err(fault);
+    }
+    let len = input.len() / 3 * 2 + input.len() % 3 / 2;
+    if output.len() < len {
+        return Err(Fault::BufferTooSmall);
+    }
+    // Every chunk of 3 symbols is a number of 2 bytes, a trailing chunk of 2 symbols of 1 byte,
+    // least significant symbol first.
+    let mut start = 0;
+    let mut written = 0;
+    while start < input.len() {
+        let end = if input.len() - start < 3 {
+            input.len()
+        } else {
+            start + 3
+        };
+        let mut value = 0u32;
+        let mut position = end;
+        while position > start {
+            position -= 1;
+            value = value * 45 + Base45::SYMBOL_TABLE[input[position] as usize] as u32;
+        }
+        let bytes = end - start - 1;
+        // The most significant symbol is too large for the bytes of the chunk.
+        if value >> (8 * bytes) != 0 {
+            return Err(Fault::Symbol(end - 1));
+        }
+        let mut index = 0;
+        while index < bytes {
+            output[written + index] = (value >> (8 * (bytes - 1 - index))) as u8;
+            index += 1;
+        }
+        written += bytes;
+        start = end;
+    }
+    Ok(len)
+}
+
+/// Proquint, pronounceable quintuplets (consonants: bdfghjklmnprstvz, vowels: aiou).
+///
+/// Every two bytes are encoded as a consonant-vowel-consonant-vowel-consonant word, words are
+/// separated by `-`. An odd trailing byte is encoded as a consonant-vowel-consonant word, the
+/// last consonant carrying 2 bits.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Proquint;
+
+impl BaseCodec for Proquint {
+    const ALPHABET: Option<&'static str> = None;
+    const RADIX: u32 = 65536;
+    const FAMILY: Family = Family::Proquint;
+
+    #[cfg(feature = "alloc")]
     fn encode<I: AsRef<[u8]>>(input: I) -> String {
-        base45::encode(input.as_ref())
+        let input = input.as_ref();
+        let mut output = vec![0; Self::encoded_len(input.len())];
+        let len =
+            Self::encode_to_slice(input, &mut output).expect("output is sized by encoded_len");
+        output.truncate(len);
+        String::from_utf8(output).expect("proquints are ASCII")
     }
 
+    #[cfg(feature = "alloc")]
     fn decode<I: AsRef<str>>(input: I) -> Result<Vec<u8>> {
-        let uppercased = input.as_ref().to_ascii_uppercase();
-        Ok(base45::decode(&uppercased)?)
+        let input = input.as_ref();
+        let mut output = vec![0; Self::decoded_len_max(input.len())];
+        let len = Self::decode_to_slice(input, &mut output)?;
+        output.truncate(len);
+        Ok(output)
+    }
+
+    fn encoded_len(input_len: usize) -> usize {
+        let words = input_len.div_ceil(2);
+        encoding::PROQUINT_PREFIX.len()
+            + input_len / 2 * 5
+            + input_len % 2 * 3
+            + words.saturating_sub(1)
+    }
+
+    fn decoded_len_max(encoded_len: usize) -> usize {
+        // A word of 5 symbols and a separator holds 2 bytes, a word of 3 symbols 1 byte.
+        encoded_len / 2
+    }
+
+    fn validate(input: &str) -> Result<()> {
+        proquint_decode(input.as_bytes(), None)
+            .map(|_| ())
+            .map_err(|fault| fault.into_error(Base::Proquint, input))
+    }
+
+    fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Result<usize> {
+        let len = Self::encoded_len(input.len());
+        let output = output.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+        let consonants = encoding::PROQUINT_CONSONANTS.as_bytes();
+        let vowels = encoding::PROQUINT_VOWELS.as_bytes();
+
+        let prefix = encoding::PROQUINT_PREFIX.len();
+        output[..prefix].copy_from_slice(encoding::PROQUINT_PREFIX.as_bytes());
+        let mut position = prefix;
+        for (index, chunk) in input.chunks(2).enumerate() {
+            if index > 0 {
+                output[position] = b'-';
+                position += 1;
+            }
+            match *chunk {
+                [high, low] => {
+                    let word = usize::from(u16::from_be_bytes([high, low]));
+                    output[position..position + 5].copy_from_slice(&[
+                        consonants[word >> 12],
+                        vowels[word >> 10 & 3],
+                        consonants[word >> 6 & 15],
+                        vowels[word >> 4 & 3],
+                        consonants[word & 15],
+                    ]);
+                    position += 5;
+                }
+                [byte] => {
+                    let byte = usize::from(byte);
+                    output[position..position + 3].copy_from_slice(&[
+                        consonants[byte >> 4],
+                        vowels[byte >> 2 & 3],
+                        consonants[byte & 3],
+                    ]);
+                    position += 3;
+                }
+                _ => unreachable!("chunks of at most 2 bytes"),
+            }
+        }
+        Ok(position)
+    }
+
+    fn decode_to_slice(input: &str, output: &mut [u8]) -> Result<usize> {
+        proquint_decode(input.as_bytes(), Some(output))
+            .map_err(|fault| fault.into_error(Base::Proquint, input))
+    }
+}
+
+/// Decode the proquint `input` into `output`, or only check it if there is no `output`. Returns
+/// the number of decoded bytes.
+///
+/// Shared with the [`multibase!`](crate::multibase!) literals, hence a `const fn`.
+pub(crate) const fn proquint_decode(
+    input: &[u8],
+    mut output: Option<&mut [u8]>,
+) -> core::result::Result<usize, Fault> {
+    let prefix = encoding::PROQUINT_PREFIX.as_bytes();
+    let mut position = 0;
+    while position < prefix.len() {
+        if position == input.len() {
+            return Err(Fault::Length);
+        }
+        if input[position] != prefix[position] {
+            return Err(Fault::Symbol(position));
+        }
+        position += 1;
+    }
+
+    let mut len = 0;
+    let mut start = prefix.len();
+    while start < input.len() {
+        let mut end = start;
+        while end < input.len() && input[end] != b'-' {
+            end += 1;
+        }
+        let last = end == input.len();
+        let (word, bytes) = match proquint_word(input, start, end, last) {
+            Ok(word) => word,
+            Err(fault) => return Err(fault),
+        };
+        if let Some(output) = &mut output {
+            if output.len() < len + bytes {
+                return Err(Fault::BufferTooSmall);
+            }
+            let mut index = 0;
+            while index < bytes {
+                output[len + index] = (word >> (8 * (bytes - 1 - index))) as u8;
+                index += 1;
+            }
+        }
+        len += bytes;
+        start = end + 1;
+        if start == input.len() {
+            // Trailing separator.
+            return Err(Fault::Symbol(end));
+        }
+    }
+    Ok(len)
+}
+
+/// Decode the proquint word `input[start..end]`, the last word of the input if `last` is set.
+/// Returns the value of the word and its number of bytes.
+const fn proquint_word(
+    input: &[u8],
+    start: usize,
+    end: usize,
+    last: bool,
+) -> core::result::Result<(u16, usize), Fault> {
+    match end - start {
+        len @ (3 | 5) if len == 5 || last => {
+            let mut word = 0;
+            let mut position = start;
+            while position < end {
+                let (table, bits) = if (position - start).is_multiple_of(2) {
+                    (&encoding::PROQUINT_CONSONANT_TABLE, 4)
+                } else {
+                    (&encoding::PROQUINT_VOWEL_TABLE, 2)
+                };
+                let value = table[input[position] as usize];
+                // The last consonant of a word of 1 byte carries 2 bits.
+                let bits = if len == 3 && position + 1 == end {
+                    2
+                } else {
+                    bits
+                };
+                if value == encoding::NOT_A_SYMBOL || value >> bits != 0 {
+                    return Err(Fault::Symbol(position));
+                }
+                word = word << bits | value as u16;
+                position += 1;
+            }
+            Ok((word, len / 2))
+        }
+        // A separator or a symbol where the word should have ended.
+        len if len > 5 => Err(Fault::Symbol(start + 5)),
+        _ if !last => Err(Fault::Symbol(end)),
+        _ => Err(Fault::Length),
     }
 }
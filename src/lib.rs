@@ -1,26 +1,112 @@
 //! # multibase
 //!
 //! Implementation of [multibase](https://github.com/multiformats/multibase) in Rust.
+//!
+//! The `std` feature is enabled by default. Without it the crate is `no_std`, the `alloc`
+//! feature enables the APIs returning `String` and `Vec`. Without `alloc`, use the slice and
+//! fixed-capacity APIs like [`encode_to_slice`] and [`encode_to_array`].
+//!
+//! The families of bases are implemented behind the default features `rfc4648`, `base-x`,
+//! `base45` and `emoji`, which needs `alloc`. The bases of a disabled feature are still
+//! recognized, but return [`Error::Unsupported`], see [`Base::is_supported`].
+//!
+//! The optional `multikey` feature adds the `multikey` module for did:key identifiers, and the
+//! optional `serde` feature the `serde` module.
 
 #![deny(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc;
 
-#[cfg(not(feature = "std"))]
-use alloc::{string::String, vec::Vec};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec, vec::Vec};
 
+mod array;
 mod base;
+pub mod bases;
+#[cfg(all(feature = "alloc", feature = "base-x"))]
+mod bignum;
+mod ct;
 mod encoding;
 mod error;
+#[cfg(feature = "alloc")]
+mod guess;
 mod impls;
+#[doc(hidden)]
+pub mod literal;
+#[cfg(feature = "alloc")]
+mod multibase;
+#[cfg(feature = "multikey")]
+pub mod multikey;
+#[cfg(feature = "alloc")]
+mod options;
+#[cfg(feature = "base-x")]
+mod radix;
+#[cfg(feature = "alloc")]
+mod registry;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod spec;
+#[cfg(all(feature = "std", feature = "rfc4648"))]
+mod stream;
+#[cfg(feature = "alloc")]
+mod transcode;
 
-pub use self::base::Base;
+pub use self::array::{ArrayBytes, ArrayString};
+pub use self::base::{Base, Family, Status};
+pub use self::bases::TypedBase;
+pub use self::ct::ct_eq;
 pub use self::error::{Error, Result};
+#[cfg(feature = "alloc")]
+pub use self::guess::{guess, Confidence};
+#[cfg(feature = "alloc")]
+pub use self::multibase::{Multibase, MultibaseStr, TypedMultibase};
+#[cfg(feature = "alloc")]
+pub use self::options::{DecodeLimits, DecodeOptions, Padding};
+#[cfg(feature = "alloc")]
+pub use self::registry::{Codec, Registry};
+#[cfg(all(feature = "std", feature = "rfc4648"))]
+pub use self::stream::{Decoder, Encoder};
+#[cfg(feature = "alloc")]
+pub use self::transcode::transcode;
+
+/// Decode a multibase string literal at compile time into a byte array.
+///
+/// A malformed literal fails the build, as does a literal of a base whose cargo feature is
+/// disabled.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::multibase;
+///
+/// const HELLO: [u8; 5] = multibase!("zCn8eVZg");
+/// assert_eq!(&HELLO, b"hello");
+/// assert_eq!(multibase!("f68656c6c6f"), HELLO);
+/// ```
+///
+/// ```compile_fail
+/// use multibase::multibase;
+///
+/// // `0` is not in the base58btc alphabet.
+/// const HELLO: [u8; 5] = multibase!("zCn8eV0g");
+/// ```
+#[macro_export]
+macro_rules! multibase {
+    ($input:expr) => {{
+        const INPUT: &str = $input;
+        const DECODED: ([u8; INPUT.len()], usize) = $crate::literal::decode(INPUT);
+        const OUTPUT: [u8; DECODED.1] = $crate::literal::truncate(&DECODED.0);
+        OUTPUT
+    }};
+}
 
 /// Decode the base string.
 ///
+/// The positions of the returned errors include the base code. Use [`DecodeOptions`] for
+/// stricter or more lenient decoding, or to limit the size of untrusted inputs.
+///
 /// # Examples
 ///
 /// ```
@@ -31,16 +117,123 @@ pub use self::error::{Error, Result};
 ///     (Base::Base58Btc, b"hello".to_vec())
 /// );
 /// ```
+#[cfg(feature = "alloc")]
 pub fn decode<T: AsRef<str>>(input: T) -> Result<(Base, Vec<u8>)> {
     let input = input.as_ref();
-    let code = input.chars().next().ok_or(Error::InvalidBaseString)?;
+    let code = input.chars().next().ok_or(Error::EmptyInput)?;
+    let base = Base::from_code(code)?;
+    let decoded = base
+        .decode(&input[code.len_utf8()..])
+        .map_err(|err| err.offset(code.len_utf8()))?;
+    Ok((base, decoded))
+}
+
+/// Decode the base string with the built-in bases and the codecs of `registry`, returns the
+/// base code and the decoded bytes.
+///
+/// See [`Registry`] for an example.
+#[cfg(feature = "alloc")]
+pub fn decode_with<T: AsRef<str>>(registry: &Registry, input: T) -> Result<(char, Vec<u8>)> {
+    registry.decode(input)
+}
+
+/// Decode the base string, or a legacy base58btc string without a base code.
+///
+/// Strings starting with the reserved codes `1` and `Q`, like CIDv0 and bare multihashes,
+/// are decoded as a whole with base58btc. Other strings are decoded like with [`decode`].
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Error, decode, decode_with_legacy};
+///
+/// let cid = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+/// assert_eq!(decode(cid), Err(Error::ReservedPrefix('Q')));
+///
+/// let (base, decoded) = decode_with_legacy(cid).unwrap();
+/// assert_eq!(base, Base::Base58Btc);
+/// assert_eq!(&decoded[..2], &[0x12, 0x20]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_with_legacy<T: AsRef<str>>(input: T) -> Result<(Base, Vec<u8>)> {
+    let input = input.as_ref();
+    match input.chars().next() {
+        Some('1') | Some('Q') => Ok((Base::Base58Btc, Base::Base58Btc.decode(input)?)),
+        _ => decode(input),
+    }
+}
+
+/// Decode the base string, rejecting it unless it is the unique encoding of its bytes.
+///
+/// Use this for content-addressed identifiers, where every value must have a single string.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Error, decode_canonical};
+///
+/// assert!(decode_canonical("f68656c6c6f").is_ok());
+/// assert_eq!(
+///     decode_canonical("f68656C6C6F"),
+///     Err(Error::NonCanonical {
+///         base: Base::Base16Lower,
+///         position: 6,
+///     })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_canonical<T: AsRef<str>>(input: T) -> Result<(Base, Vec<u8>)> {
+    let input = input.as_ref();
+    let code = input.chars().next().ok_or(Error::EmptyInput)?;
     let base = Base::from_code(code)?;
-    let decoded = base.decode(&input[code.len_utf8()..])?;
+    let decoded = base
+        .decode_canonical(&input[code.len_utf8()..])
+        .map_err(|err| err.offset(code.len_utf8()))?;
     Ok((base, decoded))
 }
 
+/// Decode the base string, rejecting bases whose status in the multibase spec is below
+/// `min_status`.
+///
+/// Use [`Status::Final`] to only accept the final bases.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Error, Status, decode_with_status};
+///
+/// assert_eq!(
+///     decode_with_status("zCn8eVZg", Status::Final).unwrap(),
+///     (Base::Base58Btc, b"hello".to_vec())
+/// );
+/// assert_eq!(
+///     decode_with_status("k5pzcszu7", Status::Final),
+///     Err(Error::RejectedStatus {
+///         base: Base::Base36Lower,
+///         status: Status::Draft,
+///     })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_with_status<T: AsRef<str>>(input: T, min_status: Status) -> Result<(Base, Vec<u8>)> {
+    let input = input.as_ref();
+    let code = input.chars().next().ok_or(Error::EmptyInput)?;
+    let base = Base::from_code(code)?;
+    let status = base.status();
+    if status < min_status {
+        return Err(Error::RejectedStatus { base, status });
+    }
+    decode(input)
+}
+
 /// Encode with the given byte slice to base string.
 ///
+/// # Panics
+///
+/// Panics if the base is [`Base::Identity`] and the input is not valid UTF-8, use
+/// [`try_encode`] or [`encode_bytes`] for arbitrary input. Panics as well if the base is not
+/// supported, see [`Base::is_supported`].
+///
 /// # Examples
 ///
 /// ```
@@ -48,9 +241,217 @@ pub fn decode<T: AsRef<str>>(input: T) -> Result<(Base, Vec<u8>)> {
 ///
 /// assert_eq!(encode(Base::Base58Btc, b"hello"), "zCn8eVZg");
 /// ```
+#[cfg(feature = "alloc")]
 pub fn encode<T: AsRef<[u8]>>(base: Base, input: T) -> String {
+    match try_encode(base, input) {
+        Err(Error::Unsupported(base)) => panic!("unsupported base: {:?}", base),
+        encoded => encoded.expect("input must be valid UTF-8 bytes"),
+    }
+}
+
+/// Encode with the given byte slice to base string, or `Error` if the base is
+/// [`Base::Identity`] and the input is not valid UTF-8, or if the base is not supported.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Error, try_encode};
+///
+/// assert_eq!(try_encode(Base::Identity, b"hello").unwrap(), "\0hello");
+/// assert_eq!(
+///     try_encode(Base::Identity, [0x68, 0xff]),
+///     Err(Error::InvalidUtf8 { position: 1 })
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn try_encode<T: AsRef<[u8]>>(base: Base, input: T) -> Result<String> {
+    let code_len = base.code().len_utf8();
+    String::from_utf8(try_encode_bytes(base, input.as_ref())?).map_err(|err| Error::InvalidUtf8 {
+        position: err.utf8_error().valid_up_to() - code_len,
+    })
+}
+
+/// Encode with the given byte slice to the bytes of a base string.
+///
+/// Unlike [`encode`], the result is not required to be a string, which allows to encode
+/// arbitrary binary with [`Base::Identity`].
+///
+/// # Panics
+///
+/// Panics if the base is not supported, see [`Base::is_supported`].
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encode_bytes};
+///
+/// assert_eq!(encode_bytes(Base::Base58Btc, b"hello"), b"zCn8eVZg");
+/// assert_eq!(encode_bytes(Base::Identity, [0xff]), [0x00, 0xff]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn encode_bytes<T: AsRef<[u8]>>(base: Base, input: T) -> Vec<u8> {
+    try_encode_bytes(base, input.as_ref())
+        .unwrap_or_else(|_| panic!("unsupported base: {:?}", base))
+}
+
+#[cfg(feature = "alloc")]
+fn try_encode_bytes(base: Base, input: &[u8]) -> Result<Vec<u8>> {
+    let mut encoded = vec![0; encoded_len(base, input.len())?];
+    let len = encode_to_slice(base, input, &mut encoded)?;
+    encoded.truncate(len);
+    Ok(encoded)
+}
+
+/// Decode the bytes of a base string.
+///
+/// Unlike [`decode`], the input is not required to be a string, which allows to decode
+/// arbitrary binary with [`Base::Identity`]. The other bases reject input that is not valid
+/// UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_bytes};
+///
+/// assert_eq!(
+///     decode_bytes(b"zCn8eVZg").unwrap(),
+///     (Base::Base58Btc, b"hello".to_vec())
+/// );
+/// assert_eq!(
+///     decode_bytes([0x00, 0xff]).unwrap(),
+///     (Base::Identity, vec![0xff])
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_bytes<T: AsRef<[u8]>>(input: T) -> Result<(Base, Vec<u8>)> {
     let input = input.as_ref();
-    let mut encoded = base.encode(input.as_ref());
-    encoded.insert(0, base.code());
-    encoded
+    match input.split_first() {
+        None => Err(Error::EmptyInput),
+        Some((0, data)) => Ok((Base::Identity, data.to_vec())),
+        Some(_) => match core::str::from_utf8(input) {
+            Ok(input) => decode(input),
+            Err(err) => {
+                let position = err.valid_up_to();
+                let valid = core::str::from_utf8(&input[..position]).expect("valid up to position");
+                let code = valid
+                    .chars()
+                    .next()
+                    .ok_or(Error::InvalidUtf8 { position })?;
+                Err(Error::InvalidSymbol {
+                    base: Base::from_code(code)?,
+                    position,
+                    char: char::REPLACEMENT_CHARACTER,
+                })
+            }
+        },
+    }
+}
+
+/// Get the length in bytes of the base string, including the code, encoding `input_len` bytes.
+///
+/// This is exact or an upper bound, see [`Base::encoded_len`]. Returns
+/// [`Error::Unsupported`] if the base is not supported.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encoded_len};
+///
+/// assert_eq!(encoded_len(Base::Base64, 32), Ok(44));
+/// ```
+pub fn encoded_len(base: Base, input_len: usize) -> Result<usize> {
+    Ok(base.code().len_utf8() + base.encoded_len(input_len)?)
+}
+
+/// Decode the base string into `output`, returns the detected base and the number of bytes
+/// written.
+///
+/// The decoded size is bounded by `output`, decoding stops with [`Error::BufferTooSmall`] as
+/// soon as it is full.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_to_slice};
+///
+/// let mut output = [0; 16];
+/// assert_eq!(
+///     decode_to_slice("zCn8eVZg", &mut output).unwrap(),
+///     (Base::Base58Btc, 5)
+/// );
+/// assert_eq!(&output[..5], b"hello");
+/// ```
+pub fn decode_to_slice<T: AsRef<str>>(input: T, output: &mut [u8]) -> Result<(Base, usize)> {
+    let input = input.as_ref();
+    let code = input.chars().next().ok_or(Error::EmptyInput)?;
+    let base = Base::from_code(code)?;
+    let len = base
+        .decode_to_slice(&input[code.len_utf8()..], output)
+        .map_err(|err| err.offset(code.len_utf8()))?;
+    Ok((base, len))
+}
+
+/// Encode the given byte slice into `output`, returns the number of bytes written.
+///
+/// Use [`encoded_len`] to size `output`.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encode_to_slice};
+///
+/// let mut output = [0; 16];
+/// let len = encode_to_slice(Base::Base58Btc, b"hello", &mut output).unwrap();
+/// assert_eq!(&output[..len], b"zCn8eVZg");
+/// ```
+pub fn encode_to_slice<T: AsRef<[u8]>>(base: Base, input: T, output: &mut [u8]) -> Result<usize> {
+    let code = base.code();
+    let code_len = code.len_utf8();
+    let prefix = output.get_mut(..code_len).ok_or(Error::BufferTooSmall)?;
+    code.encode_utf8(prefix);
+    let len = base.encode_to_slice(input, &mut output[code_len..])?;
+    Ok(code_len + len)
+}
+
+/// Encode the given byte slice to a base string of at most `N` bytes, without allocating.
+///
+/// Returns [`Error::BufferTooSmall`] if the base string may not fit, size `N` with
+/// [`encoded_len`].
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{ArrayString, Base, encode_to_array};
+///
+/// let encoded: ArrayString<16> = encode_to_array(Base::Base58Btc, b"hello").unwrap();
+/// assert_eq!(encoded, "zCn8eVZg");
+/// ```
+pub fn encode_to_array<const N: usize, T: AsRef<[u8]>>(
+    base: Base,
+    input: T,
+) -> Result<ArrayString<N>> {
+    let mut output = [0; N];
+    let len = encode_to_slice(base, input, &mut output)?;
+    let code_len = base.code().len_utf8();
+    ArrayString::from_utf8(output, len).map_err(|err| Error::InvalidUtf8 {
+        position: err.valid_up_to() - code_len,
+    })
+}
+
+/// Decode the base string into at most `N` bytes, without allocating, returns the detected
+/// base and the decoded bytes.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_to_array};
+///
+/// let (base, decoded) = decode_to_array::<16, _>("zCn8eVZg").unwrap();
+/// assert_eq!(base, Base::Base58Btc);
+/// assert_eq!(decoded, *b"hello");
+/// ```
+pub fn decode_to_array<const N: usize, T: AsRef<str>>(input: T) -> Result<(Base, ArrayBytes<N>)> {
+    let mut output = [0; N];
+    let (base, len) = decode_to_slice(input, &mut output)?;
+    Ok((base, ArrayBytes::new(output, len)))
 }
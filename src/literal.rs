@@ -0,0 +1,186 @@
+//! Decoding of multibase literals at compile time, used by the [`multibase!`](crate::multibase!)
+//! macro.
+//!
+//! Everything here is a `const fn` that panics on malformed input, which is a compile error when
+//! evaluated for a constant. The Base-X, Base45 and proquint decoders are shared with the runtime
+//! decoding, the RFC 4648 and Base256Emoji literals are decoded here.
+
+use crate::base::{Base, Family};
+#[cfg(feature = "rfc4648")]
+use crate::encoding::NOT_A_SYMBOL;
+use crate::error::Fault;
+#[cfg(feature = "base45")]
+use crate::impls::base45_decode;
+use crate::impls::proquint_decode;
+#[cfg(feature = "base-x")]
+use crate::radix;
+
+/// Decode the multibase literal `input` into a buffer of `M` bytes, returns the buffer and the
+/// decoded length.
+///
+/// No base decodes to more bytes than the length of its encoding, a buffer of `input.len()`
+/// bytes is always large enough.
+pub const fn decode<const M: usize>(input: &str) -> ([u8; M], usize) {
+    let bytes = input.as_bytes();
+    if bytes.is_empty() {
+        panic!("empty multibase literal");
+    }
+    let (code, start) = next_char(bytes, 0);
+    let base = from_code(code);
+    if !base.is_supported() {
+        panic!("the base of the multibase literal is not enabled by the cargo features");
+    }
+    let mut output = [0; M];
+    let data = bytes.split_at(start).1;
+    let len = match base.family() {
+        Family::Identity => decode_identity(data, &mut output),
+        #[cfg(feature = "rfc4648")]
+        Family::Rfc4648 => decode_bits(base, data, &mut output),
+        #[cfg(feature = "base-x")]
+        Family::BaseX => {
+            radix::decode_in_place(base.symbol_table(), base.radix(), data, &mut output)
+        }
+        #[cfg(feature = "base45")]
+        Family::Base45 => base45_decode(data, &mut output),
+        #[cfg(feature = "emoji")]
+        Family::Emoji => decode_emoji(base, data, &mut output),
+        Family::Proquint => proquint_decode(data, Some(&mut output)),
+        #[allow(unreachable_patterns)]
+        _ => unreachable!(),
+    };
+    match len {
+        Ok(len) => (output, len),
+        Err(Fault::Symbol(_)) => panic!("invalid symbol in multibase literal"),
+        Err(Fault::Length) => panic!("invalid length of multibase literal"),
+        Err(Fault::BufferTooSmall) => panic!("multibase literal decodes to too many bytes"),
+    }
+}
+
+/// Copy the first `N` bytes of `buffer`.
+pub const fn truncate<const M: usize, const N: usize>(buffer: &[u8; M]) -> [u8; N] {
+    let mut output = [0; N];
+    let mut index = 0;
+    while index < N {
+        output[index] = buffer[index];
+        index += 1;
+    }
+    output
+}
+
+/// Decode the UTF-8 char starting at `position`, returns its code point and the position of the
+/// next char.
+const fn next_char(bytes: &[u8], position: usize) -> (u32, usize) {
+    let first = bytes[position] as u32;
+    let (mut code, len) = if first < 0x80 {
+        (first, 1)
+    } else if first < 0xe0 {
+        (first & 0x1f, 2)
+    } else if first < 0xf0 {
+        (first & 0x0f, 3)
+    } else {
+        (first & 0x07, 4)
+    };
+    let mut index = 1;
+    while index < len {
+        code = code << 6 | (bytes[position + index] & 0x3f) as u32;
+        index += 1;
+    }
+    (code, position + len)
+}
+
+/// Get the base of the code point `code`.
+const fn from_code(code: u32) -> Base {
+    let mut index = 0;
+    while index < Base::ALL.len() {
+        if Base::ALL[index].code() as u32 == code {
+            return Base::ALL[index];
+        }
+        index += 1;
+    }
+    panic!("unknown base code in multibase literal")
+}
+
+const fn decode_identity(input: &[u8], output: &mut [u8]) -> Result<usize, Fault> {
+    let mut index = 0;
+    while index < input.len() {
+        output[index] = input[index];
+        index += 1;
+    }
+    Ok(input.len())
+}
+
+#[cfg(feature = "rfc4648")]
+const fn decode_bits(base: Base, input: &[u8], output: &mut [u8]) -> Result<usize, Fault> {
+    let table = base.symbol_table();
+    let bit = base.radix().trailing_zeros() as usize;
+    let mut end = input.len();
+    if let (Some(pad), Some((_, symbols))) = (base.padding(), base.block_size()) {
+        if !end.is_multiple_of(symbols) {
+            return Err(Fault::Length);
+        }
+        while end > 0 && input[end - 1] == pad as u8 {
+            end -= 1;
+        }
+        if end < input.len() && end.is_multiple_of(symbols) {
+            return Err(Fault::Symbol(end));
+        }
+    }
+    if end * bit % 8 >= bit {
+        return Err(Fault::Length);
+    }
+
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    let mut len = 0;
+    let mut index = 0;
+    while index < end {
+        let value = table[input[index] as usize];
+        if value == NOT_A_SYMBOL {
+            return Err(Fault::Symbol(index));
+        }
+        buffer = buffer << bit | value as u32;
+        bits += bit;
+        if bits >= 8 {
+            bits -= 8;
+            output[len] = (buffer >> bits) as u8;
+            len += 1;
+            buffer &= (1 << bits) - 1;
+        }
+        index += 1;
+    }
+    // Non-zero trailing bits.
+    if buffer != 0 {
+        return Err(Fault::Symbol(end - 1));
+    }
+    Ok(len)
+}
+
+#[cfg(feature = "emoji")]
+const fn decode_emoji(base: Base, input: &[u8], output: &mut [u8]) -> Result<usize, Fault> {
+    let alphabet = match base.alphabet() {
+        Some(alphabet) => alphabet.as_bytes(),
+        None => panic!("base has no alphabet"),
+    };
+    let mut len = 0;
+    let mut index = 0;
+    while index < input.len() {
+        let (symbol, next) = next_char(input, index);
+        let mut value = 0;
+        let mut position = 0;
+        loop {
+            if position == alphabet.len() {
+                return Err(Fault::Symbol(index));
+            }
+            let (expected, next) = next_char(alphabet, position);
+            if expected == symbol {
+                break;
+            }
+            value += 1;
+            position = next;
+        }
+        output[len] = value;
+        len += 1;
+        index = next;
+    }
+    Ok(len)
+}
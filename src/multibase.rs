@@ -0,0 +1,423 @@
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use crate::base::Base;
+use crate::bases::TypedBase;
+use crate::error::{Error, Result};
+use crate::options::DecodeOptions;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
+/// Check that `input` starts with a known base code and that the rest is in its alphabet.
+fn validate(input: &str) -> Result<Base> {
+    let code = input.chars().next().ok_or(Error::EmptyInput)?;
+    let base = Base::from_code(code)?;
+    base.validate(&input[code.len_utf8()..])
+        .map_err(|err| err.offset(code.len_utf8()))?;
+    Ok(base)
+}
+
+/// Split a validated multibase string into its base and encoded data.
+fn split(input: &str) -> (Base, &str) {
+    let code = input.chars().next().expect("multibase string is validated");
+    let base = Base::from_code(code).expect("multibase string is validated");
+    (base, &input[code.len_utf8()..])
+}
+
+/// An owned multibase string.
+///
+/// The base code and the alphabet of the encoded data are validated on construction, the data
+/// is only decoded on demand.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Multibase};
+///
+/// let multibase: Multibase = "zCn8eVZg".parse().unwrap();
+/// assert_eq!(multibase.base(), Base::Base58Btc);
+/// assert_eq!(multibase.payload_str(), "Cn8eVZg");
+/// assert_eq!(multibase.decode().unwrap(), b"hello");
+///
+/// assert!("z0OIl".parse::<Multibase>().is_err());
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub struct Multibase(String);
+
+impl Multibase {
+    /// Encode the given byte slice to a multibase string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the base is [`Base::Identity`] and the input is not valid UTF-8, or if the
+    /// base is not supported, see [`Base::is_supported`].
+    pub fn encode<I: AsRef<[u8]>>(base: Base, input: I) -> Self {
+        Self(crate::encode(base, input))
+    }
+
+    /// Get the base of the multibase string.
+    pub fn base(&self) -> Base {
+        self.as_multibase_str().base()
+    }
+
+    /// Get the encoded data, without the base code.
+    pub fn payload_str(&self) -> &str {
+        split(&self.0).1
+    }
+
+    /// Decode the encoded data.
+    ///
+    /// The size of the data is not limited, use [`Multibase::decode_with_options`] with
+    /// [`DecodeLimits`](crate::DecodeLimits) for untrusted inputs.
+    pub fn decode(&self) -> Result<Vec<u8>> {
+        self.as_multibase_str().decode()
+    }
+
+    /// Decode the encoded data with the given options.
+    pub fn decode_with_options(&self, options: &DecodeOptions) -> Result<Vec<u8>> {
+        self.as_multibase_str().decode_with_options(options)
+    }
+
+    /// Get the whole multibase string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Borrow as a [`MultibaseStr`].
+    pub fn as_multibase_str(&self) -> MultibaseStr<'_> {
+        MultibaseStr(&self.0)
+    }
+
+    /// Unwrap the multibase string.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for Multibase {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        MultibaseStr::new(s).map(MultibaseStr::to_multibase)
+    }
+}
+
+impl TryFrom<String> for Multibase {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        validate(&s)?;
+        Ok(Self(s))
+    }
+}
+
+impl From<Multibase> for String {
+    fn from(multibase: Multibase) -> Self {
+        multibase.0
+    }
+}
+
+impl AsRef<str> for Multibase {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Multibase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A borrowed multibase string.
+///
+/// This is the borrowed counterpart of [`Multibase`], validated the same way.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, MultibaseStr};
+///
+/// let multibase = MultibaseStr::new("f68656c6c6f").unwrap();
+/// assert_eq!(multibase.base(), Base::Base16Lower);
+/// assert_eq!(multibase.decode().unwrap(), b"hello");
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct MultibaseStr<'a>(&'a str);
+
+impl<'a> MultibaseStr<'a> {
+    /// Validate the given multibase string.
+    pub fn new(s: &'a str) -> Result<Self> {
+        validate(s)?;
+        Ok(Self(s))
+    }
+
+    /// Get the base of the multibase string.
+    pub fn base(&self) -> Base {
+        split(self.0).0
+    }
+
+    /// Get the encoded data, without the base code.
+    pub fn payload_str(&self) -> &'a str {
+        split(self.0).1
+    }
+
+    /// Decode the encoded data.
+    ///
+    /// The size of the data is not limited, use [`MultibaseStr::decode_with_options`] with
+    /// [`DecodeLimits`](crate::DecodeLimits) for untrusted inputs.
+    pub fn decode(&self) -> Result<Vec<u8>> {
+        crate::decode(self.0).map(|(_, decoded)| decoded)
+    }
+
+    /// Decode the encoded data with the given options.
+    pub fn decode_with_options(&self, options: &DecodeOptions) -> Result<Vec<u8>> {
+        options.decode(self.0).map(|(_, decoded)| decoded)
+    }
+
+    /// Get the whole multibase string.
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// Copy into an owned [`Multibase`].
+    pub fn to_multibase(self) -> Multibase {
+        Multibase(self.0.to_owned())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for MultibaseStr<'a> {
+    type Error = Error;
+
+    fn try_from(s: &'a str) -> Result<Self> {
+        Self::new(s)
+    }
+}
+
+impl AsRef<str> for MultibaseStr<'_> {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl fmt::Display for MultibaseStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl PartialEq<MultibaseStr<'_>> for Multibase {
+    fn eq(&self, other: &MultibaseStr<'_>) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl PartialEq<Multibase> for MultibaseStr<'_> {
+    fn eq(&self, other: &Multibase) -> bool {
+        self.0 == other.0
+    }
+}
+
+/// An owned multibase string that can only hold the base `B`, see [`bases`](crate::bases).
+///
+/// Parsing rejects the other bases with [`Error::WrongBase`], e.g. for protocols that always
+/// use the same base.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::bases::Base58Btc;
+/// use multibase::{Base, Error, TypedMultibase};
+///
+/// let key: TypedMultibase<Base58Btc> = "zCn8eVZg".parse().unwrap();
+/// assert_eq!(key.decode().unwrap(), b"hello");
+///
+/// assert_eq!(
+///     "f68656c6c6f".parse::<TypedMultibase<Base58Btc>>(),
+///     Err(Error::WrongBase {
+///         expected: Base::Base58Btc,
+///         found: Base::Base16Lower
+///     })
+/// );
+/// ```
+pub struct TypedMultibase<B> {
+    multibase: Multibase,
+    base: PhantomData<B>,
+}
+
+impl<B: TypedBase> TypedMultibase<B> {
+    /// Encode the given byte slice to a multibase string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the base is [`Base::Identity`] and the input is not valid UTF-8, or if the
+    /// base is not supported, see [`Base::is_supported`].
+    pub fn encode<I: AsRef<[u8]>>(input: I) -> Self {
+        Self::new_unchecked(Multibase::encode(B::BASE, input))
+    }
+
+    fn new_unchecked(multibase: Multibase) -> Self {
+        Self {
+            multibase,
+            base: PhantomData,
+        }
+    }
+
+    /// Get the base of the multibase string.
+    pub fn base(&self) -> Base {
+        B::BASE
+    }
+
+    /// Get the encoded data, without the base code.
+    pub fn payload_str(&self) -> &str {
+        &self.multibase.0[B::CODE.len_utf8()..]
+    }
+
+    /// Decode the encoded data.
+    ///
+    /// The size of the data is not limited, use [`TypedMultibase::decode_with_options`] with
+    /// [`DecodeLimits`](crate::DecodeLimits) for untrusted inputs.
+    pub fn decode(&self) -> Result<Vec<u8>> {
+        B::BASE
+            .decode(self.payload_str())
+            .map_err(|err| err.offset(B::CODE.len_utf8()))
+    }
+
+    /// Decode the encoded data with the given options.
+    pub fn decode_with_options(&self, options: &DecodeOptions) -> Result<Vec<u8>> {
+        B::BASE
+            .decode_with_options(self.payload_str(), options)
+            .map_err(|err| err.offset(B::CODE.len_utf8()))
+    }
+
+    /// Get the whole multibase string.
+    pub fn as_str(&self) -> &str {
+        self.multibase.as_str()
+    }
+
+    /// Borrow as a [`Multibase`].
+    pub fn as_multibase(&self) -> &Multibase {
+        &self.multibase
+    }
+
+    /// Unwrap the [`Multibase`].
+    pub fn into_multibase(self) -> Multibase {
+        self.multibase
+    }
+
+    /// Unwrap the multibase string.
+    pub fn into_string(self) -> String {
+        self.multibase.0
+    }
+}
+
+impl<B: TypedBase> FromStr for TypedMultibase<B> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.parse::<Multibase>().and_then(Self::try_from)
+    }
+}
+
+impl<B: TypedBase> TryFrom<String> for TypedMultibase<B> {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        Multibase::try_from(s).and_then(Self::try_from)
+    }
+}
+
+impl<B: TypedBase> TryFrom<Multibase> for TypedMultibase<B> {
+    type Error = Error;
+
+    fn try_from(multibase: Multibase) -> Result<Self> {
+        let found = multibase.base();
+        if found != B::BASE {
+            return Err(Error::WrongBase {
+                expected: B::BASE,
+                found,
+            });
+        }
+        Ok(Self::new_unchecked(multibase))
+    }
+}
+
+impl<B> From<TypedMultibase<B>> for Multibase {
+    fn from(typed: TypedMultibase<B>) -> Self {
+        typed.multibase
+    }
+}
+
+impl<B> From<TypedMultibase<B>> for String {
+    fn from(typed: TypedMultibase<B>) -> Self {
+        typed.multibase.0
+    }
+}
+
+impl<B> AsRef<str> for TypedMultibase<B> {
+    fn as_ref(&self) -> &str {
+        &self.multibase.0
+    }
+}
+
+impl<B> fmt::Display for TypedMultibase<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.multibase.0)
+    }
+}
+
+impl<B> fmt::Debug for TypedMultibase<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TypedMultibase")
+            .field(&self.multibase.0)
+            .finish()
+    }
+}
+
+// Implemented by hand, the derives would require `B` to implement the traits.
+
+impl<B> Clone for TypedMultibase<B> {
+    fn clone(&self) -> Self {
+        Self {
+            multibase: self.multibase.clone(),
+            base: PhantomData,
+        }
+    }
+}
+
+impl<B> PartialEq for TypedMultibase<B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.multibase == other.multibase
+    }
+}
+
+impl<B> Eq for TypedMultibase<B> {}
+
+impl<B> PartialOrd for TypedMultibase<B> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<B> Ord for TypedMultibase<B> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.multibase.cmp(&other.multibase)
+    }
+}
+
+impl<B> Hash for TypedMultibase<B> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.multibase.hash(state);
+    }
+}
+
+impl<B> PartialEq<Multibase> for TypedMultibase<B> {
+    fn eq(&self, other: &Multibase) -> bool {
+        self.multibase == *other
+    }
+}
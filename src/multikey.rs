@@ -0,0 +1,255 @@
+//! Public keys as [Multikey](https://www.w3.org/TR/controller-document/#multikey)
+//! `publicKeyMultibase` values and [did:key](https://w3c-ccg.github.io/did-method-key/)
+//! identifiers.
+//!
+//! The key is prefixed with the unsigned varint of its multicodec code and encoded with
+//! base58btc.
+//!
+//! # Examples
+//!
+//! ```
+//! use multibase::multikey::{self, KeyType};
+//!
+//! let key = [0x42; 32];
+//! let did = multikey::encode_did_key(KeyType::Ed25519, key).unwrap();
+//! assert!(did.starts_with("did:key:z6Mk"));
+//!
+//! let (key_type, decoded) = multikey::decode(&did).unwrap();
+//! assert_eq!(key_type, KeyType::Ed25519);
+//! assert_eq!(decoded, key);
+//! ```
+
+use core::fmt;
+
+use crate::base::Base;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+
+/// The prefix of did:key identifiers.
+pub const DID_KEY_PREFIX: &str = "did:key:";
+
+/// The maximum length in bytes of an unsigned varint, holding 63 bits.
+const MAX_VARINT_LEN: usize = 9;
+
+/// Type alias to use the multikey [`Error`] type in a `Result`.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Error types of the multikey functions.
+///
+/// Positions are byte offsets into the decoded string, including the did:key prefix.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The multibase string is invalid or not in base58btc.
+    Multibase(crate::Error),
+    /// The unsigned varint is truncated, longer than 9 bytes or not minimally encoded.
+    InvalidVarint,
+    /// The multicodec code is not a known key type, see [`KeyType`].
+    UnknownMulticodec(u64),
+    /// The key does not have the length of its key type.
+    InvalidKeyLength {
+        /// The length in bytes of the key.
+        len: usize,
+        /// The length in bytes of the keys of its key type.
+        expected: usize,
+    },
+}
+
+impl From<crate::Error> for Error {
+    fn from(err: crate::Error) -> Self {
+        Self::Multibase(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Multibase(err) => err.fmt(f),
+            Error::InvalidVarint => write!(f, "Invalid unsigned varint"),
+            Error::UnknownMulticodec(code) => write!(f, "Unknown multicodec: 0x{:x}", code),
+            Error::InvalidKeyLength { len, expected } => write!(
+                f,
+                "Invalid key length: {} bytes, expected {}",
+                len, expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Multibase(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// The type of a public key, identified by its multicodec code.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum KeyType {
+    /// Ed25519 public key, 32 bytes.
+    Ed25519,
+    /// X25519 public key, 32 bytes.
+    X25519,
+    /// secp256k1 public key, 33 bytes SEC1 compressed.
+    Secp256k1,
+    /// P-256 public key, 33 bytes SEC1 compressed.
+    P256,
+}
+
+impl KeyType {
+    /// All the key types.
+    pub const ALL: &'static [KeyType] = &[Self::Ed25519, Self::X25519, Self::Secp256k1, Self::P256];
+
+    /// Get the multicodec code of the key type.
+    pub const fn codec(&self) -> u64 {
+        match self {
+            Self::Ed25519 => 0xed,
+            Self::X25519 => 0xec,
+            Self::Secp256k1 => 0xe7,
+            Self::P256 => 0x1200,
+        }
+    }
+
+    /// Get the multicodec name of the key type.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Ed25519 => "ed25519-pub",
+            Self::X25519 => "x25519-pub",
+            Self::Secp256k1 => "secp256k1-pub",
+            Self::P256 => "p256-pub",
+        }
+    }
+
+    /// Get the length in bytes of a key.
+    pub const fn key_len(&self) -> usize {
+        match self {
+            Self::Ed25519 | Self::X25519 => 32,
+            Self::Secp256k1 | Self::P256 => 33,
+        }
+    }
+
+    /// Convert a multicodec code to the matching key type, or `Error` if no key type is
+    /// matching.
+    pub fn from_codec(codec: u64) -> Result<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|key_type| key_type.codec() == codec)
+            .ok_or(Error::UnknownMulticodec(codec))
+    }
+
+    /// Check that `len` is the length of a key.
+    fn check_len(&self, len: usize) -> Result<()> {
+        if len != self.key_len() {
+            return Err(Error::InvalidKeyLength {
+                len,
+                expected: self.key_len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for KeyType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Decode a `publicKeyMultibase` value or a did:key identifier, returns the key type and the
+/// key.
+///
+/// The positions of the returned errors include the did:key prefix and the base code.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::multikey::{self, KeyType};
+///
+/// let (key_type, key) =
+///     multikey::decode("z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F").unwrap();
+/// assert_eq!(key_type, KeyType::X25519);
+/// assert_eq!(key.len(), 32);
+/// ```
+pub fn decode<T: AsRef<str>>(input: T) -> Result<(KeyType, Vec<u8>)> {
+    let input = input.as_ref();
+    let multibase = input.strip_prefix(DID_KEY_PREFIX).unwrap_or(input);
+    let code = multibase.chars().next().ok_or(crate::Error::EmptyInput)?;
+    let base = Base::from_code(code)?;
+    if base != Base::Base58Btc {
+        return Err(Error::Multibase(crate::Error::WrongBase {
+            expected: Base::Base58Btc,
+            found: base,
+        }));
+    }
+    let offset = input.len() - multibase.len() + code.len_utf8();
+    let mut key = base
+        .decode(&input[offset..])
+        .map_err(|err| err.offset(offset))?;
+
+    let (codec, len) = read_varint(&key)?;
+    let key_type = KeyType::from_codec(codec)?;
+    key.drain(..len);
+    key_type.check_len(key.len())?;
+    Ok((key_type, key))
+}
+
+/// Encode a key as a `publicKeyMultibase` value, or `Error` if the key has the wrong length.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::multikey::{self, KeyType};
+///
+/// let encoded = multikey::encode(KeyType::P256, [0x02; 33]).unwrap();
+/// assert!(encoded.starts_with("zDn"));
+/// assert!(multikey::encode(KeyType::P256, [0x02; 32]).is_err());
+/// ```
+pub fn encode<T: AsRef<[u8]>>(key_type: KeyType, key: T) -> Result<String> {
+    let key = key.as_ref();
+    key_type.check_len(key.len())?;
+    let mut bytes = Vec::with_capacity(MAX_VARINT_LEN + key.len());
+    write_varint(key_type.codec(), &mut bytes);
+    bytes.extend_from_slice(key);
+    Ok(crate::try_encode(Base::Base58Btc, bytes)?)
+}
+
+/// Encode a key as a did:key identifier, or `Error` if the key has the wrong length.
+pub fn encode_did_key<T: AsRef<[u8]>>(key_type: KeyType, key: T) -> Result<String> {
+    let mut did = String::from(DID_KEY_PREFIX);
+    did.push_str(&encode(key_type, key)?);
+    Ok(did)
+}
+
+/// Read the unsigned varint at the start of `input`, returns its value and its length.
+///
+/// Only the minimal encoding of at most 9 bytes is accepted, like the multiformats
+/// unsigned-varint spec requires.
+fn read_varint(input: &[u8]) -> Result<(u64, usize)> {
+    let mut value = 0;
+    for (index, &byte) in input.iter().enumerate().take(MAX_VARINT_LEN) {
+        value |= u64::from(byte & 0x7f) << (7 * index);
+        if byte & 0x80 == 0 {
+            // A zero last byte adds nothing but length.
+            if byte == 0 && index > 0 {
+                return Err(Error::InvalidVarint);
+            }
+            return Ok((value, index + 1));
+        }
+    }
+    Err(Error::InvalidVarint)
+}
+
+/// Append the unsigned varint of `value` to `output`.
+fn write_varint(mut value: u64, output: &mut Vec<u8>) {
+    while value >= 0x80 {
+        output.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
@@ -0,0 +1,303 @@
+//! Configurable decoding, from strict interop checks to lenient parsing of human input.
+
+use crate::base::Base;
+use crate::error::{Error, Result};
+
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+/// How the padding of the padded bases is handled when decoding.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Padding {
+    /// The padding must be present.
+    Required,
+    /// The padding may be present or omitted.
+    Optional,
+    /// The padding must be omitted.
+    Forbidden,
+}
+
+/// Limits on the size of the base strings to decode, checked before decoding.
+///
+/// The output length is bounded with [`Base::decoded_len_max`] of the input length, hence
+/// inputs that may decode to more than `max_output_len` bytes are rejected.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{DecodeLimits, DecodeOptions, Error};
+///
+/// let options = DecodeOptions::new().limits(DecodeLimits::new(1024, 64));
+/// assert!(options.decode("zCn8eVZg").is_ok());
+/// assert_eq!(
+///     options.decode(format!("z{}", "1".repeat(100))),
+///     Err(Error::OutputTooLong { len: 100, max: 64 })
+/// );
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct DecodeLimits {
+    /// The maximum length in bytes of the input.
+    pub max_input_len: usize,
+    /// The maximum length in bytes of the decoded data.
+    pub max_output_len: usize,
+}
+
+impl DecodeLimits {
+    /// No limits, the default.
+    pub const UNLIMITED: Self = Self::new(usize::MAX, usize::MAX);
+
+    /// Create limits on the input and output lengths in bytes.
+    pub const fn new(max_input_len: usize, max_output_len: usize) -> Self {
+        Self {
+            max_input_len,
+            max_output_len,
+        }
+    }
+
+    /// Check the length of `input`, including its base code if any.
+    fn check_input(&self, input: &str) -> Result<()> {
+        if input.len() > self.max_input_len {
+            return Err(Error::InputTooLong {
+                len: input.len(),
+                max: self.max_input_len,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check the lengths of the encoded data of `base`.
+    fn check(&self, base: Base, input: &str) -> Result<()> {
+        self.check_input(input)?;
+        let len = base.decoded_len_max(input.len())?;
+        if len > self.max_output_len {
+            return Err(Error::OutputTooLong {
+                len,
+                max: self.max_output_len,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+/// Options to decode base strings.
+///
+/// The default options match [`decode`](crate::decode) and [`Base::decode`]: letters are
+/// decoded regardless of their case where the base allows it, padding is required for the
+/// padded bases, whitespace is rejected, non-zero trailing bits are rejected and the size of
+/// the input is not limited.
+///
+/// Error positions refer to the input, except that positions after a newline removed by
+/// [`DecodeOptions::whitespace`] are in the input without its newlines.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, DecodeOptions};
+///
+/// let lenient = DecodeOptions::lenient();
+/// assert_eq!(
+///     lenient.decode(" MaGVsbG8\n").unwrap(),
+///     (Base::Base64Pad, b"hello".to_vec())
+/// );
+///
+/// let strict = DecodeOptions::strict();
+/// assert!(strict.decode("F68656C6C6F").is_ok());
+/// assert!(strict.decode("F68656c6c6f").is_err());
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct DecodeOptions {
+    case_insensitive: bool,
+    padding: Padding,
+    whitespace: bool,
+    check_trailing_bits: bool,
+    limits: DecodeLimits,
+}
+
+impl DecodeOptions {
+    /// Create the default options.
+    pub const fn new() -> Self {
+        Self {
+            case_insensitive: true,
+            padding: Padding::Required,
+            whitespace: false,
+            check_trailing_bits: true,
+            limits: DecodeLimits::UNLIMITED,
+        }
+    }
+
+    /// Create options for interop with other implementations: the exact case of the alphabet,
+    /// required padding, no whitespace and zero trailing bits.
+    pub const fn strict() -> Self {
+        Self::new().case_insensitive(false)
+    }
+
+    /// Create options for human input: any case where the base allows it, optional padding,
+    /// surrounding whitespace and newlines, and any trailing bits.
+    pub const fn lenient() -> Self {
+        Self::new()
+            .padding(Padding::Optional)
+            .whitespace(true)
+            .check_trailing_bits(false)
+    }
+
+    /// Decode letters regardless of their case, for the bases that allow it (see
+    /// [`Base::is_case_insensitive`]).
+    pub const fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Set how the padding of the padded bases is handled.
+    pub const fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Ignore surrounding whitespace and newlines within the input.
+    ///
+    /// Whitespace that is a symbol of the base, like the space of Base45, is kept.
+    pub const fn whitespace(mut self, whitespace: bool) -> Self {
+        self.whitespace = whitespace;
+        self
+    }
+
+    /// Reject non-zero trailing bits in the last symbol.
+    pub const fn check_trailing_bits(mut self, check_trailing_bits: bool) -> Self {
+        self.check_trailing_bits = check_trailing_bits;
+        self
+    }
+
+    /// Limit the size of the input and of the decoded data, see [`DecodeLimits`].
+    pub const fn limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Decode the base string, see [`decode`](crate::decode).
+    pub fn decode<T: AsRef<str>>(&self, input: T) -> Result<(Base, Vec<u8>)> {
+        let input = input.as_ref();
+        self.limits.check_input(input)?;
+        let trimmed = if self.whitespace {
+            input.trim_start()
+        } else {
+            input
+        };
+        let code = trimmed.chars().next().ok_or(Error::EmptyInput)?;
+        let base = Base::from_code(code)?;
+        let offset = input.len() - trimmed.len() + code.len_utf8();
+        let decoded = self
+            .decode_base(base, &trimmed[code.len_utf8()..])
+            .map_err(|err| err.offset(offset))?;
+        Ok((base, decoded))
+    }
+
+    /// Decode the encoded data of `base`, see [`Base::decode_with_options`].
+    pub(crate) fn decode_base(&self, base: Base, input: &str) -> Result<Vec<u8>> {
+        self.limits.check(base, input)?;
+        if *self == Self::new() {
+            return base.decode(input);
+        }
+
+        if !self.whitespace {
+            return self.decode_normalized(base, Cow::Borrowed(input));
+        }
+        // Whitespace that is a symbol of the base, like the space of Base45, is data.
+        let alphabet = base.alphabet().unwrap_or_default();
+        let ignored = |char: char| char.is_whitespace() && !alphabet.contains(char);
+        let trimmed = input.trim_matches(ignored);
+        let offset = input.len() - input.trim_start_matches(ignored).len();
+        let normalized = if trimmed.contains(['\n', '\r']) {
+            Cow::Owned(
+                trimmed
+                    .chars()
+                    .filter(|&c| c != '\n' && c != '\r')
+                    .collect(),
+            )
+        } else {
+            Cow::Borrowed(trimmed)
+        };
+        self.decode_normalized(base, normalized)
+            .map_err(|err| err.offset(offset))
+    }
+
+    fn decode_normalized(&self, base: Base, mut input: Cow<'_, str>) -> Result<Vec<u8>> {
+        if !self.case_insensitive && base.is_case_insensitive() {
+            let alphabet = base.alphabet().unwrap_or_default();
+            let invalid = input
+                .char_indices()
+                .find(|&(_, char)| char.is_ascii_alphabetic() && !alphabet.contains(char));
+            if let Some((position, char)) = invalid {
+                return Err(Error::InvalidSymbol {
+                    base,
+                    position,
+                    char,
+                });
+            }
+        }
+
+        let len = input.len();
+        if let (Some(pad), Some((_, symbols))) = (base.padding(), base.block_size()) {
+            if self.padding == Padding::Forbidden {
+                if let Some(position) = input.find(pad) {
+                    return Err(Error::InvalidPadding { base, position });
+                }
+            }
+            let missing = (symbols - len % symbols) % symbols;
+            if self.padding != Padding::Required && !input.ends_with(pad) && missing > 0 {
+                input.to_mut().extend(core::iter::repeat_n(pad, missing));
+            }
+        }
+
+        let result = match base.decode(&input) {
+            Err(Error::TrailingBits { position, .. }) if !self.check_trailing_bits => {
+                clear_trailing_bits(base, input.to_mut(), position);
+                base.decode(&input)
+            }
+            result => result,
+        };
+        // Errors in the added padding are due to the length of the input.
+        result.map_err(|err| match err {
+            Error::InvalidLength { base, .. } => Error::InvalidLength { base, len },
+            Error::InvalidPadding { base, position } if position >= len => {
+                Error::InvalidLength { base, len }
+            }
+            err => err,
+        })
+    }
+}
+
+/// Clear the trailing bits of the last data symbol of `input`, at `position`.
+fn clear_trailing_bits(base: Base, input: &mut String, position: usize) {
+    let alphabet = base.alphabet().unwrap_or_default();
+    let Some(char) = input[position..].chars().next() else {
+        return;
+    };
+    let value = alphabet
+        .find(char)
+        .or_else(|| alphabet.find(char.to_ascii_uppercase()))
+        .or_else(|| alphabet.find(char.to_ascii_lowercase()));
+    let Some(value) = value else {
+        return;
+    };
+    let bit = base.radix().trailing_zeros() as usize;
+    let symbols = input.trim_end_matches(|c| Some(c) == base.padding()).len();
+    let trailing = symbols * bit % 8;
+    let cleared = value & !((1 << trailing) - 1);
+    let symbol = &alphabet[cleared..cleared + 1];
+    input.replace_range(position..position + char.len_utf8(), symbol);
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
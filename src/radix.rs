@@ -0,0 +1,249 @@
+//! Radix conversion for the bases that are not block-aligned (Base10, Base36 and Base58).
+//!
+//! Leading zero bytes map to leading zero symbols, the rest of the input is converted as one
+//! big-endian number. Short inputs are converted in place on the output slice, longer inputs
+//! with the sub-quadratic conversion of `bignum`, which needs `alloc`.
+
+use crate::base::Base;
+#[cfg(feature = "alloc")]
+use crate::bignum;
+use crate::encoding::NOT_A_SYMBOL;
+use crate::error::{Error, Fault, Result};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+
+/// Above this length in bytes or symbols, the slice functions use the sub-quadratic conversion.
+#[cfg(feature = "alloc")]
+const IN_PLACE_THRESHOLD: usize = 256;
+
+/// Upper bound of the encoded length of `input_len` bytes in the given radix.
+pub(crate) fn encoded_len(radix: usize, input_len: usize) -> usize {
+    // log2(radix) rounded down in thousandths, this overestimates the number of symbols.
+    let log2_radix = match radix {
+        10 => 3321,
+        36 => 5169,
+        58 => 5857,
+        _ => unreachable!("unsupported radix"),
+    };
+    (input_len as u128 * 8000 / log2_radix) as usize + 1
+}
+
+/// Upper bound of the decoded length of `encoded_len` symbols.
+///
+/// Every radix is smaller than 256, hence no symbol encodes more than one byte.
+pub(crate) fn decoded_len_max(encoded_len: usize) -> usize {
+    encoded_len
+}
+
+/// Get the largest power of `radix` fitting a `u64` limb, and its exponent.
+#[cfg(feature = "alloc")]
+fn big_radix(radix: u64) -> (u64, usize) {
+    let mut big = radix;
+    let mut exponent = 1;
+    while let Some(next) = big.checked_mul(radix) {
+        big = next;
+        exponent += 1;
+    }
+    (big, exponent)
+}
+
+/// Encode `input` with the given `alphabet`.
+#[cfg(feature = "alloc")]
+pub(crate) fn encode(alphabet: &[u8], input: &[u8]) -> String {
+    let radix = alphabet.len() as u64;
+    let (big, exponent) = big_radix(radix);
+    let zeros = input.iter().take_while(|&&byte| byte == 0).count();
+    let digits = bignum::to_digits(bignum::from_be_bytes(&input[zeros..]), big);
+
+    let mut output = Vec::with_capacity(zeros + digits.len() * exponent);
+    output.resize(zeros, alphabet[0]);
+    let mut symbols = [0; 64];
+    for (index, &digit) in digits.iter().enumerate() {
+        let mut digit = digit;
+        for symbol in symbols[..exponent].iter_mut().rev() {
+            *symbol = alphabet[(digit % radix) as usize];
+            digit /= radix;
+        }
+        // The first digit is not zero, but may have leading zero symbols.
+        let start = if index == 0 {
+            symbols
+                .iter()
+                .take_while(|&&symbol| symbol == alphabet[0])
+                .count()
+        } else {
+            0
+        };
+        output.extend_from_slice(&symbols[start..exponent]);
+    }
+    String::from_utf8(output).expect("alphabets are ASCII")
+}
+
+/// Encode `input` with the given `alphabet` into `output`, returns the number of bytes written.
+pub(crate) fn encode_to_slice(alphabet: &[u8], input: &[u8], output: &mut [u8]) -> Result<usize> {
+    #[cfg(feature = "alloc")]
+    if input.len() > IN_PLACE_THRESHOLD {
+        let encoded = encode(alphabet, input);
+        let output = output
+            .get_mut(..encoded.len())
+            .ok_or(Error::BufferTooSmall)?;
+        output.copy_from_slice(encoded.as_bytes());
+        return Ok(encoded.len());
+    }
+
+    let radix = alphabet.len() as u32;
+    let zeros = input.iter().take_while(|&&byte| byte == 0).count();
+
+    // Little-endian digits of the number, converted to symbols and reversed at the end.
+    let mut len = 0;
+    for &byte in &input[zeros..] {
+        let mut carry = u32::from(byte);
+        for digit in &mut output[..len] {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % radix) as u8;
+            carry /= radix;
+        }
+        while carry > 0 {
+            *output.get_mut(len).ok_or(Error::BufferTooSmall)? = (carry % radix) as u8;
+            len += 1;
+            carry /= radix;
+        }
+    }
+    if output.len() < len + zeros {
+        return Err(Error::BufferTooSmall);
+    }
+    output[len..len + zeros].fill(0);
+    len += zeros;
+
+    let output = &mut output[..len];
+    output.reverse();
+    for digit in output.iter_mut() {
+        *digit = alphabet[usize::from(*digit)];
+    }
+    Ok(len)
+}
+
+/// Decode `input` with the alphabet of `base`.
+#[cfg(feature = "alloc")]
+pub(crate) fn decode(base: Base, input: &str) -> Result<Vec<u8>> {
+    let table = base.symbol_table();
+    let radix = u64::from(base.radix());
+    let (big, exponent) = big_radix(radix);
+    let zeros = input
+        .bytes()
+        .take_while(|&symbol| table[usize::from(symbol)] == 0)
+        .count();
+
+    // Digits of `exponent` symbols, aligned to the end of the input.
+    let symbols = &input.as_bytes()[zeros..];
+    let head = symbols.len() % exponent;
+    let mut digits = Vec::with_capacity(symbols.len() / exponent + 1);
+    let mut position = zeros;
+    for chunk in core::iter::once(&symbols[..head]).chain(symbols[head..].chunks(exponent)) {
+        let mut digit = 0;
+        for _ in chunk {
+            digit = digit * radix + u64::from(symbol_value(base, input, position)?);
+            position += 1;
+        }
+        digits.push(digit);
+    }
+
+    let mut output = Vec::with_capacity(decoded_len_max(input.len()));
+    output.resize(zeros, 0);
+    bignum::extend_be_bytes(&bignum::from_digits(&digits, big), &mut output);
+    Ok(output)
+}
+
+/// Decode `input` with the alphabet of `base` into `output`, returns the number of bytes
+/// written.
+pub(crate) fn decode_to_slice(base: Base, input: &str, output: &mut [u8]) -> Result<usize> {
+    #[cfg(feature = "alloc")]
+    if input.len() > IN_PLACE_THRESHOLD {
+        let decoded = decode(base, input)?;
+        let output = output
+            .get_mut(..decoded.len())
+            .ok_or(Error::BufferTooSmall)?;
+        output.copy_from_slice(&decoded);
+        return Ok(decoded.len());
+    }
+
+    decode_in_place(base.symbol_table(), base.radix(), input.as_bytes(), output)
+        .map_err(|fault| fault.into_error(base, input))
+}
+
+/// Decode `input` with the symbol `table` of a base of the given `radix` into `output`,
+/// returns the number of bytes written.
+///
+/// The conversion is quadratic, in place on `output`. Shared with the
+/// [`multibase!`](crate::multibase!) literals, hence a `const fn`.
+pub(crate) const fn decode_in_place(
+    table: &[u8; 256],
+    radix: u32,
+    input: &[u8],
+    output: &mut [u8],
+) -> core::result::Result<usize, Fault> {
+    let mut zeros = 0;
+    while zeros < input.len() && table[input[zeros] as usize] == 0 {
+        zeros += 1;
+    }
+
+    // Little-endian bytes of the number, reversed at the end.
+    let mut len = 0;
+    let mut position = zeros;
+    while position < input.len() {
+        let value = table[input[position] as usize];
+        if value == NOT_A_SYMBOL {
+            return Err(Fault::Symbol(position));
+        }
+        let mut carry = value as u32;
+        let mut index = 0;
+        while index < len {
+            carry += output[index] as u32 * radix;
+            output[index] = carry as u8;
+            carry >>= 8;
+            index += 1;
+        }
+        while carry > 0 {
+            if len == output.len() {
+                return Err(Fault::BufferTooSmall);
+            }
+            output[len] = carry as u8;
+            len += 1;
+            carry >>= 8;
+        }
+        position += 1;
+    }
+    if output.len() < len + zeros {
+        return Err(Fault::BufferTooSmall);
+    }
+    while zeros > 0 {
+        output[len] = 0;
+        len += 1;
+        zeros -= 1;
+    }
+
+    let mut index = 0;
+    while index < len / 2 {
+        let byte = output[index];
+        output[index] = output[len - 1 - index];
+        output[len - 1 - index] = byte;
+        index += 1;
+    }
+    Ok(len)
+}
+
+/// Check that every symbol of `input` is part of the alphabet of `base`.
+pub(crate) fn validate(base: Base, input: &str) -> Result<()> {
+    for position in 0..input.len() {
+        symbol_value(base, input, position)?;
+    }
+    Ok(())
+}
+
+/// Get the value of the symbol at `position` of `input`.
+fn symbol_value(base: Base, input: &str, position: usize) -> Result<u8> {
+    match base.symbol_table()[usize::from(input.as_bytes()[position])] {
+        NOT_A_SYMBOL => Err(Fault::Symbol(position).into_error(base, input)),
+        value => Ok(value),
+    }
+}
@@ -0,0 +1,132 @@
+//! User-defined bases, dispatched by their code next to the built-in ones.
+
+use core::fmt;
+
+use crate::base::Base;
+use crate::error::{Error, Result};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+/// A user-defined base, see [`Registry`].
+///
+/// The input passed to the codec does not include the base code.
+pub trait Codec {
+    /// Encode the given byte slice to base string.
+    fn encode(&self, input: &[u8]) -> String;
+
+    /// Decode the base string.
+    fn decode(&self, input: &str) -> Result<Vec<u8>>;
+}
+
+/// A set of user-defined bases, keyed by their code.
+///
+/// The codes of the built-in bases and the codes reserved by the multibase spec cannot be
+/// registered.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{decode_with, Base, Codec, Error, Registry, Result};
+///
+/// /// Base2 with the symbols `.` and `-`.
+/// struct Morse;
+///
+/// impl Codec for Morse {
+///     fn encode(&self, input: &[u8]) -> String {
+///         Base::Base2.encode(input).replace('0', ".").replace('1', "-")
+///     }
+///
+///     fn decode(&self, input: &str) -> Result<Vec<u8>> {
+///         Base::Base2.decode(input.replace('.', "0").replace('-', "1"))
+///     }
+/// }
+///
+/// let mut registry = Registry::new();
+/// registry.register('~', Morse).unwrap();
+/// assert_eq!(registry.register('z', Morse), Err(Error::CodeInUse('z')));
+///
+/// let encoded = registry.encode('~', b"a").unwrap();
+/// assert_eq!(encoded, "~.--....-");
+/// assert_eq!(decode_with(&registry, &encoded).unwrap(), ('~', b"a".to_vec()));
+/// assert_eq!(decode_with(&registry, "zCn8eVZg").unwrap(), ('z', b"hello".to_vec()));
+/// ```
+#[derive(Default)]
+pub struct Registry {
+    codecs: BTreeMap<char, Box<dyn Codec + Send + Sync>>,
+}
+
+impl Registry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `codec` for the base `code`.
+    ///
+    /// Returns `Error` if the code is already used by a built-in base or a registered codec,
+    /// or reserved by the multibase spec.
+    pub fn register<C>(&mut self, code: char, codec: C) -> Result<()>
+    where
+        C: Codec + Send + Sync + 'static,
+    {
+        match Base::from_code(code) {
+            Ok(_) => return Err(Error::CodeInUse(code)),
+            Err(Error::UnknownBase(_)) => {}
+            Err(err) => return Err(err),
+        }
+        if self.codecs.contains_key(&code) {
+            return Err(Error::CodeInUse(code));
+        }
+        self.codecs.insert(code, Box::new(codec));
+        Ok(())
+    }
+
+    /// Get the codec registered for the base `code`.
+    pub fn get(&self, code: char) -> Option<&(dyn Codec + Send + Sync)> {
+        self.codecs.get(&code).map(|codec| &**codec)
+    }
+
+    /// Iterate over the registered codes.
+    pub fn codes(&self) -> impl Iterator<Item = char> + '_ {
+        self.codecs.keys().copied()
+    }
+
+    /// Encode with the given byte slice to base string, with the codec registered for `code`
+    /// or the built-in base of `code`.
+    pub fn encode<T: AsRef<[u8]>>(&self, code: char, input: T) -> Result<String> {
+        match self.get(code) {
+            Some(codec) => {
+                let mut encoded = String::from(code);
+                encoded.push_str(&codec.encode(input.as_ref()));
+                Ok(encoded)
+            }
+            None => crate::try_encode(Base::from_code(code)?, input),
+        }
+    }
+
+    /// Decode the base string with the built-in base or the registered codec of its code,
+    /// returns the code and the decoded bytes.
+    pub fn decode<T: AsRef<str>>(&self, input: T) -> Result<(char, Vec<u8>)> {
+        let input = input.as_ref();
+        let code = input.chars().next().ok_or(Error::EmptyInput)?;
+        match self.get(code) {
+            Some(codec) => {
+                let decoded = codec
+                    .decode(&input[code.len_utf8()..])
+                    .map_err(|err| err.offset(code.len_utf8()))?;
+                Ok((code, decoded))
+            }
+            None => crate::decode(input).map(|(base, decoded)| (base.code(), decoded)),
+        }
+    }
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.codes()).finish()
+    }
+}
@@ -0,0 +1,316 @@
+//! [Serde](https://serde.rs) helpers to (de)serialize bytes as multibase strings.
+//!
+//! Each module is named after a base in the multibase spec and is meant to be used with
+//! `#[serde(with = "...")]` on `Vec<u8>` and `[u8; N]` fields. Human-readable formats get the
+//! multibase string, binary formats get the raw bytes. Deserializing a string of another base is
+//! an error, use [`any`] to accept every base.
+//!
+//! The size of the data is not limited by `deserialize`, the `deserialize_max` functions reject
+//! data longer than `MAX` bytes before decoding it, for untrusted inputs:
+//!
+//! ```
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Signature(
+//!     #[serde(deserialize_with = "multibase::serde::any::deserialize_max::<64, _, _>")] Vec<u8>,
+//! );
+//!
+//! assert!(serde_json::from_str::<Signature>(r#""f776f726c64""#).is_ok());
+//! assert!(serde_json::from_str::<Signature>(&format!(r#""f{}""#, "00".repeat(65))).is_err());
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Key {
+//!     #[serde(with = "multibase::serde::base58btc")]
+//!     public_key: [u8; 5],
+//!     #[serde(
+//!         serialize_with = "multibase::serde::base64url::serialize",
+//!         deserialize_with = "multibase::serde::any::deserialize"
+//!     )]
+//!     signature: Vec<u8>,
+//! }
+//!
+//! let key = Key {
+//!     public_key: *b"hello",
+//!     signature: b"world".to_vec(),
+//! };
+//! let json = serde_json::to_string(&key).unwrap();
+//! assert_eq!(json, r#"{"public_key":"zCn8eVZg","signature":"ud29ybGQ"}"#);
+//!
+//! let key: Key = serde_json::from_str(r#"{"public_key":"zCn8eVZg","signature":"f776f726c64"}"#).unwrap();
+//! assert_eq!(key.signature, b"world");
+//! ```
+
+use core::convert::TryFrom;
+use core::fmt;
+
+use ::serde::de::{self, Deserializer, SeqAccess, Unexpected, Visitor};
+use ::serde::{Deserialize, Serialize, Serializer};
+
+use crate::base::Base;
+use crate::error::{Error, Result as MultibaseResult};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+/// Serialize `bytes` as a multibase string of `base` in human-readable formats, as is otherwise.
+fn serialize<S: Serializer>(base: Base, bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        let encoded = crate::try_encode(base, bytes).map_err(::serde::ser::Error::custom)?;
+        serializer.serialize_str(&encoded)
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// Deserialize at most `max_len` bytes from a multibase string in human-readable formats, as
+/// is otherwise.
+///
+/// If `base` is given, multibase strings of other bases are rejected.
+fn deserialize<'de, T, D>(
+    base: Option<Base>,
+    max_len: usize,
+    deserializer: D,
+) -> Result<T, D::Error>
+where
+    T: TryFrom<Vec<u8>>,
+    D: Deserializer<'de>,
+{
+    let bytes = if deserializer.is_human_readable() {
+        deserializer.deserialize_str(MultibaseVisitor { base, max_len })?
+    } else {
+        deserializer.deserialize_bytes(BytesVisitor { max_len })?
+    };
+    let len = bytes.len();
+    T::try_from(bytes).map_err(|_| de::Error::invalid_length(len, &"bytes of a different length"))
+}
+
+/// Decode the multibase string `input` of at most `max_len` bytes of data.
+///
+/// Strings longer than the encoding of `max_len` bytes are rejected before decoding.
+fn decode_max(input: &str, max_len: usize) -> MultibaseResult<(Base, Vec<u8>)> {
+    let code = input.chars().next().ok_or(Error::EmptyInput)?;
+    let base = Base::from_code(code)?;
+    let max_input_len = base.encoded_len(max_len)?.saturating_add(code.len_utf8());
+    if input.len() > max_input_len {
+        return Err(Error::InputTooLong {
+            len: input.len(),
+            max: max_input_len,
+        });
+    }
+    let (base, bytes) = crate::decode(input)?;
+    check_len(bytes.len(), max_len)?;
+    Ok((base, bytes))
+}
+
+/// Check that `len` bytes are at most `max_len` bytes.
+fn check_len(len: usize, max_len: usize) -> MultibaseResult<()> {
+    if len > max_len {
+        return Err(Error::OutputTooLong { len, max: max_len });
+    }
+    Ok(())
+}
+
+struct MultibaseVisitor {
+    base: Option<Base>,
+    max_len: usize,
+}
+
+impl<'de> Visitor<'de> for MultibaseVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.base {
+            Some(base) => write!(f, "a {} multibase string", base.name()),
+            None => write!(f, "a multibase string"),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        let (base, bytes) = if self.max_len == usize::MAX {
+            crate::decode(v)
+        } else {
+            decode_max(v, self.max_len)
+        }
+        .map_err(E::custom)?;
+        match self.base {
+            Some(expected) if expected != base => {
+                Err(de::Error::invalid_value(Unexpected::Str(v), &self))
+            }
+            _ => Ok(bytes),
+        }
+    }
+}
+
+struct BytesVisitor {
+    max_len: usize,
+}
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        check_len(v.len(), self.max_len).map_err(E::custom)?;
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        check_len(v.len(), self.max_len).map_err(E::custom)?;
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let capacity = seq.size_hint().unwrap_or(0).min(self.max_len);
+        let mut bytes = Vec::with_capacity(capacity);
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+            check_len(bytes.len(), self.max_len).map_err(de::Error::custom)?;
+        }
+        Ok(bytes)
+    }
+}
+
+macro_rules! build_serde_modules {
+    ( $( $name:ident => $base:ident, )* ) => {
+        $(
+            #[doc = concat!("(De)serialize bytes as `", stringify!($name), "` multibase strings.")]
+            pub mod $name {
+                use core::convert::TryFrom;
+
+                use ::serde::{Deserializer, Serializer};
+
+                use crate::base::Base;
+
+                #[cfg(all(feature = "alloc", not(feature = "std")))]
+                use alloc::vec::Vec;
+
+                /// Serialize bytes as a multibase string, or as is in binary formats.
+                pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    T: AsRef<[u8]> + ?Sized,
+                    S: Serializer,
+                {
+                    super::serialize(Base::$base, bytes.as_ref(), serializer)
+                }
+
+                /// Deserialize bytes from a multibase string, or as is in binary formats.
+                pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+                where
+                    T: TryFrom<Vec<u8>>,
+                    D: Deserializer<'de>,
+                {
+                    super::deserialize(Some(Base::$base), usize::MAX, deserializer)
+                }
+
+                /// Deserialize at most `MAX` bytes from a multibase string, or as is in binary
+                /// formats. Longer strings are rejected before decoding.
+                pub fn deserialize_max<'de, const MAX: usize, T, D>(
+                    deserializer: D,
+                ) -> Result<T, D::Error>
+                where
+                    T: TryFrom<Vec<u8>>,
+                    D: Deserializer<'de>,
+                {
+                    super::deserialize(Some(Base::$base), MAX, deserializer)
+                }
+            }
+        )*
+    };
+}
+
+build_serde_modules! {
+    identity => Identity,
+    base2 => Base2,
+    base8 => Base8,
+    base10 => Base10,
+    base16 => Base16Lower,
+    base16upper => Base16Upper,
+    base32 => Base32Lower,
+    base32upper => Base32Upper,
+    base32pad => Base32PadLower,
+    base32padupper => Base32PadUpper,
+    base32hex => Base32HexLower,
+    base32hexupper => Base32HexUpper,
+    base32hexpad => Base32HexPadLower,
+    base32hexpadupper => Base32HexPadUpper,
+    base32z => Base32Z,
+    base36 => Base36Lower,
+    base36upper => Base36Upper,
+    base45 => Base45,
+    base58flickr => Base58Flickr,
+    base58btc => Base58Btc,
+    base64 => Base64,
+    base64pad => Base64Pad,
+    base64url => Base64Url,
+    base64urlpad => Base64UrlPad,
+    proquint => Proquint,
+    base256emoji => Base256Emoji,
+}
+
+/// Deserialize bytes from a multibase string of any base.
+///
+/// There is no `serialize` as a base has to be chosen, pair it with the `serialize` of one of
+/// the base modules.
+pub mod any {
+    use core::convert::TryFrom;
+
+    use ::serde::Deserializer;
+
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec::Vec;
+
+    /// Deserialize bytes from a multibase string, or as is in binary formats.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        super::deserialize(None, usize::MAX, deserializer)
+    }
+
+    /// Deserialize at most `MAX` bytes from a multibase string, or as is in binary formats.
+    /// Longer strings are rejected before decoding.
+    pub fn deserialize_max<'de, const MAX: usize, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        super::deserialize(None, MAX, deserializer)
+    }
+}
+
+impl Serialize for Base {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Base {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(BaseVisitor)
+    }
+}
+
+struct BaseVisitor;
+
+impl<'de> Visitor<'de> for BaseVisitor {
+    type Value = Base;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a multibase base name")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Base::from_name(v).map_err(|_| de::Error::invalid_value(Unexpected::Str(v), &self))
+    }
+}
@@ -0,0 +1,73 @@
+//! The table of the multibase spec, embedded from a copy of the upstream `multibase.csv`.
+//!
+//! The table is parsed at compile time, a base missing from it fails the build.
+
+use crate::base::Status;
+
+/// The upstream `multibase.csv` table: encoding, code, description and status.
+pub(crate) const TABLE: &str = include_str!("multibase.csv");
+
+/// Get the status of the encoding `name` in the table.
+pub(crate) const fn status(name: &str) -> Status {
+    let table = TABLE.as_bytes();
+    let mut start = 0;
+    while start < table.len() {
+        let mut end = start;
+        while end < table.len() && table[end] != b'\n' {
+            end += 1;
+        }
+        let mut comma = start;
+        while comma < end && table[comma] != b',' {
+            comma += 1;
+        }
+        if field_eq(table, start, comma, name.as_bytes()) {
+            let mut last = end;
+            while last > start && table[last - 1] != b',' {
+                last -= 1;
+            }
+            return if field_eq(table, last, end, b"final") {
+                Status::Final
+            } else if field_eq(table, last, end, b"draft") {
+                Status::Draft
+            } else if field_eq(table, last, end, b"experimental") {
+                Status::Experimental
+            } else {
+                panic!("unknown status in multibase.csv")
+            };
+        }
+        start = end + 1;
+    }
+    panic!("encoding missing from multibase.csv")
+}
+
+/// Check whether the base code `code` is reserved in the table.
+pub(crate) fn is_reserved(code: char) -> bool {
+    TABLE.lines().any(|line| {
+        let mut fields = line.split(',').map(str::trim);
+        let mut chars = fields.nth(1).unwrap_or_default().chars();
+        chars.next() == Some(code)
+            && chars.next().is_none()
+            && fields.next_back() == Some("reserved")
+    })
+}
+
+/// Check whether `table[start..end]`, trimmed of ASCII whitespace, equals `value`.
+const fn field_eq(table: &[u8], mut start: usize, mut end: usize, value: &[u8]) -> bool {
+    while start < end && table[start].is_ascii_whitespace() {
+        start += 1;
+    }
+    while end > start && table[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    if end - start != value.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < value.len() {
+        if table[start + index] != value[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
@@ -0,0 +1,387 @@
+//! Streaming encoding and decoding for the block-aligned bases.
+
+use std::io::{self, Read, Write};
+
+use data_encoding::Encoding;
+
+use crate::base::Base;
+use crate::error::{Error, Result};
+use crate::options::DecodeLimits;
+
+/// Upper bound of input bytes encoded in one go, keeps the scratch buffer small.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+const WRITER_TAKEN: &str = "writer is only taken by finish";
+
+/// Get the encoding backing `base` and its block size in bytes and symbols, or reject it as
+/// not streamable.
+fn streaming_encoding(base: Base) -> Result<(Encoding, (usize, usize))> {
+    match (base.encoding(), base.block_size()) {
+        (Some(encoding), Some(block_size)) => Ok((encoding, block_size)),
+        _ => Err(Error::NotStreamable(base)),
+    }
+}
+
+/// Wrap a multibase error into an I/O error.
+fn invalid_data(err: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Streaming multibase encoder.
+///
+/// Writes the base code followed by the encoded data to the wrapped writer. Only the
+/// block-aligned bases (Base2, Base8, Base16, Base32 and Base64 variants) can be streamed.
+///
+/// The trailing partial block and the padding are written by [`Encoder::finish`]. Dropping the
+/// encoder finishes it as well, but any error is silently ignored.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use multibase::{Base, Encoder};
+///
+/// let mut encoder = Encoder::new(Base::Base64Pad, Vec::new()).unwrap();
+/// encoder.write_all(b"hello ").unwrap();
+/// encoder.write_all(b"world").unwrap();
+/// let encoded = encoder.finish().unwrap();
+/// assert_eq!(encoded, b"MaGVsbG8gd29ybGQ=");
+/// ```
+pub struct Encoder<W: Write> {
+    base: Base,
+    encoding: Encoding,
+    writer: Option<W>,
+    block_len: usize,
+    pending: [u8; 8],
+    pending_len: usize,
+    prefix_written: bool,
+    scratch: Vec<u8>,
+}
+
+impl<W: Write> Encoder<W> {
+    /// Create an encoder writing `base` encoded data into `writer`.
+    ///
+    /// Returns [`Error::NotStreamable`] if the base is not block-aligned.
+    pub fn new(base: Base, writer: W) -> Result<Self> {
+        let (encoding, (block_len, _)) = streaming_encoding(base)?;
+        Ok(Self {
+            base,
+            encoding,
+            writer: Some(writer),
+            block_len,
+            pending: [0; 8],
+            pending_len: 0,
+            prefix_written: false,
+            scratch: Vec::new(),
+        })
+    }
+
+    /// Get the base the data is encoded with.
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Get a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        self.writer.as_ref().expect(WRITER_TAKEN)
+    }
+
+    /// Encode the trailing partial block, including padding, and return the wrapped writer.
+    ///
+    /// The writer is dropped if this fails, the trailing block is not written again on drop.
+    pub fn finish(mut self) -> io::Result<W> {
+        let result = self.write_final();
+        let writer = self.writer.take().expect(WRITER_TAKEN);
+        result.map(|()| writer)
+    }
+
+    fn writer_mut(&mut self) -> &mut W {
+        self.writer.as_mut().expect(WRITER_TAKEN)
+    }
+
+    fn write_prefix(&mut self) -> io::Result<()> {
+        if !self.prefix_written {
+            let mut code = [0; 4];
+            let code = self.base.code().encode_utf8(&mut code);
+            self.write_encoded_raw(code.as_bytes())?;
+            self.prefix_written = true;
+        }
+        Ok(())
+    }
+
+    fn write_encoded_raw(&mut self, encoded: &[u8]) -> io::Result<()> {
+        self.writer_mut().write_all(encoded)
+    }
+
+    fn write_encoded(&mut self, input: &[u8]) -> io::Result<()> {
+        let mut scratch = core::mem::take(&mut self.scratch);
+        scratch.resize(self.encoding.encode_len(input.len()), 0);
+        self.encoding.encode_mut(input, &mut scratch);
+        let result = self.write_encoded_raw(&scratch);
+        self.scratch = scratch;
+        result
+    }
+
+    fn write_final(&mut self) -> io::Result<()> {
+        self.write_prefix()?;
+        let pending = self.pending;
+        self.write_encoded(&pending[..self.pending_len])?;
+        self.pending_len = 0;
+        self.writer_mut().flush()
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_prefix()?;
+
+        // Complete a partial block left over from a previous write first.
+        if self.pending_len > 0 {
+            let take = (self.block_len - self.pending_len).min(buf.len());
+            self.pending[self.pending_len..self.pending_len + take].copy_from_slice(&buf[..take]);
+            self.pending_len += take;
+            if self.pending_len == self.block_len {
+                let pending = self.pending;
+                self.write_encoded(&pending[..self.block_len])?;
+                self.pending_len = 0;
+            }
+            return Ok(take);
+        }
+
+        let aligned = buf.len().min(CHUNK_SIZE) / self.block_len * self.block_len;
+        if aligned == 0 {
+            self.pending[..buf.len()].copy_from_slice(buf);
+            self.pending_len = buf.len();
+            return Ok(buf.len());
+        }
+        self.write_encoded(&buf[..aligned])?;
+        Ok(aligned)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer_mut().flush()
+    }
+}
+
+impl<W: Write> Drop for Encoder<W> {
+    fn drop(&mut self) {
+        if self.writer.is_some() && !std::thread::panicking() {
+            let _ = self.write_final();
+        }
+    }
+}
+
+/// Streaming multibase decoder.
+///
+/// Reads the base code from the wrapped reader on construction and yields the decoded data
+/// as it is read. Only the block-aligned bases (Base2, Base8, Base16, Base32 and Base64
+/// variants) can be streamed.
+///
+/// Invalid input is reported as an [`io::Error`] of kind [`io::ErrorKind::InvalidData`]
+/// wrapping the multibase [`Error`]. The size of the input is not limited, use
+/// [`Decoder::with_limits`] for untrusted inputs.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+/// use multibase::{Base, Decoder};
+///
+/// let mut decoder = Decoder::new(&b"MaGVsbG8gd29ybGQ="[..]).unwrap();
+/// assert_eq!(decoder.base(), Base::Base64Pad);
+///
+/// let mut decoded = Vec::new();
+/// decoder.read_to_end(&mut decoded).unwrap();
+/// assert_eq!(decoded, b"hello world");
+/// ```
+pub struct Decoder<R: Read> {
+    base: Base,
+    encoding: Encoding,
+    reader: R,
+    symbols_len: usize,
+    input: Vec<u8>,
+    /// Position of the first buffered input byte, including the base code.
+    position: usize,
+    output: Vec<u8>,
+    output_pos: usize,
+    /// Number of bytes decoded so far.
+    decoded: usize,
+    limits: DecodeLimits,
+    finished: bool,
+}
+
+impl<R: Read> Decoder<R> {
+    /// Create a decoder reading multibase encoded data from `reader`.
+    ///
+    /// The base code is read right away, [`Error::NotStreamable`] is returned if the detected
+    /// base is not block-aligned.
+    pub fn new(reader: R) -> io::Result<Self> {
+        Self::with_limits(reader, DecodeLimits::UNLIMITED)
+    }
+
+    /// Create a decoder reading multibase encoded data from `reader`, rejecting inputs over
+    /// the given limits.
+    ///
+    /// The limits are checked as the input is read, [`Error::InputTooLong`] or
+    /// [`Error::OutputTooLong`] is returned as soon as the input read or the data decoded so
+    /// far exceed them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Read;
+    /// use multibase::{DecodeLimits, Decoder};
+    ///
+    /// let limits = DecodeLimits::new(1024, 4);
+    /// let mut decoder = Decoder::with_limits(&b"MaGVsbG8gd29ybGQ="[..], limits).unwrap();
+    /// assert!(decoder.read_to_end(&mut Vec::new()).is_err());
+    /// ```
+    pub fn with_limits(mut reader: R, limits: DecodeLimits) -> io::Result<Self> {
+        let code = read_code(&mut reader)?;
+        let base = Base::from_code(code).map_err(invalid_data)?;
+        let (encoding, (_, symbols_len)) = streaming_encoding(base).map_err(invalid_data)?;
+        Ok(Self {
+            base,
+            encoding,
+            reader,
+            symbols_len,
+            input: Vec::new(),
+            position: code.len_utf8(),
+            output: Vec::new(),
+            output_pos: 0,
+            decoded: 0,
+            limits,
+            finished: false,
+        })
+    }
+
+    /// Get the base detected from the code.
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Get a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Unwrap the decoder, returning the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Read the next chunk of input and decode all whole blocks of it.
+    ///
+    /// A trailing partial block is kept until the end of the input is reached.
+    fn fill(&mut self) -> io::Result<()> {
+        let start = self.input.len();
+        self.input.resize(start + CHUNK_SIZE, 0);
+        let read = loop {
+            match self.reader.read(&mut self.input[start..]) {
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        };
+        let read = match read {
+            Ok(read) => read,
+            Err(err) => {
+                self.input.truncate(start);
+                return Err(err);
+            }
+        };
+        self.input.truncate(start + read);
+        let input_len = self.position + self.input.len();
+        if input_len > self.limits.max_input_len {
+            return Err(invalid_data(Error::InputTooLong {
+                len: input_len,
+                max: self.limits.max_input_len,
+            }));
+        }
+
+        let aligned = if read == 0 {
+            self.finished = true;
+            self.input.len()
+        } else {
+            self.input.len() / self.symbols_len * self.symbols_len
+        };
+        let encoded = &self.input[..aligned];
+        let decoded_len = self
+            .encoding
+            .decode_len(encoded.len())
+            .map_err(|err| self.decode_error(encoded, err))?;
+        if self.decoded + decoded_len > self.limits.max_output_len {
+            return Err(invalid_data(Error::OutputTooLong {
+                len: self.decoded + decoded_len,
+                max: self.limits.max_output_len,
+            }));
+        }
+        self.output.resize(decoded_len, 0);
+        let written = self
+            .encoding
+            .decode_mut(encoded, &mut self.output)
+            .map_err(|partial| self.decode_error(encoded, partial.error))?;
+        self.output.truncate(written);
+        self.output_pos = 0;
+        self.decoded += written;
+        self.input.drain(..aligned);
+        self.position += aligned;
+        Ok(())
+    }
+
+    /// Convert an error raised while decoding the `encoded` chunk of the input.
+    fn decode_error(&self, encoded: &[u8], err: data_encoding::DecodeError) -> io::Error {
+        let err = match Error::from_data_encoding(self.base, encoded, err) {
+            Error::InvalidLength { base, len } => Error::InvalidLength {
+                base,
+                len: self.position - self.base.code().len_utf8() + len,
+            },
+            err => err.offset(self.position),
+        };
+        invalid_data(err)
+    }
+}
+
+impl<R: Read> Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.output_pos == self.output.len() {
+            if self.finished {
+                return Ok(0);
+            }
+            self.fill()?;
+        }
+        let len = buf.len().min(self.output.len() - self.output_pos);
+        buf[..len].copy_from_slice(&self.output[self.output_pos..self.output_pos + len]);
+        self.output_pos += len;
+        Ok(len)
+    }
+}
+
+/// Read the UTF-8 encoded base code from the start of `reader`.
+fn read_code<R: Read>(reader: &mut R) -> io::Result<char> {
+    let mut code = [0; 4];
+    reader.read_exact(&mut code[..1]).map_err(|err| {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            invalid_data(Error::EmptyInput)
+        } else {
+            err
+        }
+    })?;
+    let len = match code[0] {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return Err(invalid_data(Error::InvalidUtf8 { position: 0 })),
+    };
+    reader.read_exact(&mut code[1..len]).map_err(|err| {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            invalid_data(Error::InvalidUtf8 { position: 0 })
+        } else {
+            err
+        }
+    })?;
+    core::str::from_utf8(&code[..len])
+        .ok()
+        .and_then(|code| code.chars().next())
+        .ok_or_else(|| invalid_data(Error::InvalidUtf8 { position: 0 }))
+}
@@ -0,0 +1,130 @@
+//! Transcoding of base strings from one base to another.
+
+use crate::base::{Base, Family};
+use crate::error::{Error, Result};
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+
+/// Transcode the base string to `target`.
+///
+/// Bases with the same alphabet up to the case of its letters, like Base32Lower and
+/// Base32PadUpper, are transcoded symbol by symbol without decoding: the symbols are checked
+/// against the alphabet of the source base, the letters are converted to the case of `target`
+/// and the padding is stripped or added. The other bases, and invalid strings, are decoded and
+/// encoded again. The positions of the returned errors include the base code.
+///
+/// Returns [`Error::Unsupported`] if the source or the target base is not supported.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, transcode};
+///
+/// assert_eq!(transcode("BNBSWY3DP", Base::Base32Lower).unwrap(), "bnbswy3dp");
+/// assert_eq!(transcode("maGk", Base::Base64Pad).unwrap(), "MaGk=");
+/// assert_eq!(transcode("f68656c6c6f", Base::Base58Btc).unwrap(), "zCn8eVZg");
+/// ```
+pub fn transcode(input: &str, target: Base) -> Result<String> {
+    let code = input.chars().next().ok_or(Error::EmptyInput)?;
+    let source = Base::from_code(code)?;
+    for base in [source, target] {
+        if !base.is_supported() {
+            return Err(Error::Unsupported(base));
+        }
+    }
+    let data = &input[code.len_utf8()..];
+    if is_symbol_compatible(source, target) {
+        let stripped = strip_padding(source, data).filter(|data| is_valid(source, data));
+        if let Some(data) = stripped {
+            return Ok(transcode_symbols(source, target, data));
+        }
+    }
+
+    // Decoding also locates the error of invalid strings.
+    let decoded = source
+        .decode(data)
+        .map_err(|err| err.offset(code.len_utf8()))?;
+    crate::try_encode(target, decoded)
+}
+
+/// Transcode the valid and unpadded `data` symbol by symbol.
+fn transcode_symbols(source: Base, target: Base, data: &str) -> String {
+    let padding = target.block_size().map_or(0, |(_, symbols)| symbols);
+    let mut output = String::with_capacity(target.code().len_utf8() + data.len() + padding);
+    output.push(target.code());
+    let start = output.len();
+    output.push_str(data);
+    if source.is_case_insensitive() {
+        let uppercase = target
+            .alphabet()
+            .is_some_and(|alphabet| alphabet.bytes().any(|symbol| symbol.is_ascii_uppercase()));
+        if uppercase {
+            output[start..].make_ascii_uppercase();
+        } else {
+            output[start..].make_ascii_lowercase();
+        }
+    }
+    if let (Some(pad), Some((_, symbols))) = (target.padding(), target.block_size()) {
+        while !(output.len() - start).is_multiple_of(symbols) {
+            output.push(pad);
+        }
+    }
+    output
+}
+
+/// Strip the padding of `data`, `None` if `source` is padded and `data` is not padded to a
+/// whole number of blocks.
+fn strip_padding(source: Base, data: &str) -> Option<&str> {
+    match (source.padding(), source.block_size()) {
+        (Some(pad), Some((_, symbols))) => {
+            let stripped = data.trim_end_matches(pad);
+            (stripped.len().div_ceil(symbols) * symbols == data.len()).then_some(stripped)
+        }
+        _ => Some(data),
+    }
+}
+
+/// Check whether the unpadded `data` is a valid string of `source`, symbol by symbol.
+///
+/// Any string of symbols is valid for the radix bases. The bit-aligned bases also constrain the
+/// length, and the unused low bits of the last symbol must be zero.
+fn is_valid(source: Base, data: &str) -> bool {
+    let table = source.symbol_table();
+    let symbols = data.as_bytes();
+    // The values of the symbols are below 128 and `NOT_A_SYMBOL` has the high bit set, so the
+    // symbols are checked without a branch per symbol.
+    let values = symbols
+        .iter()
+        .fold(0, |values, &symbol| values | table[usize::from(symbol)]);
+    if values & 0x80 != 0 {
+        return false;
+    }
+    if source.family() != Family::Rfc4648 {
+        return true;
+    }
+    let bit = source.radix().trailing_zeros() as usize;
+    let unused = symbols.len() * bit % 8;
+    unused < bit
+        && symbols
+            .last()
+            .is_none_or(|&symbol| table[usize::from(symbol)] & ((1 << unused) - 1) == 0)
+}
+
+/// Check whether the symbols of `source` map one to one to the symbols of `target`, up to the
+/// case of the letters and the padding.
+fn is_symbol_compatible(source: Base, target: Base) -> bool {
+    let family = source.family();
+    if family != target.family() || !matches!(family, Family::Rfc4648 | Family::BaseX) {
+        return false;
+    }
+    match (source.alphabet(), target.alphabet()) {
+        (Some(source_alphabet), Some(target_alphabet)) => {
+            source_alphabet == target_alphabet
+                || (source.is_case_insensitive()
+                    && target.is_case_insensitive()
+                    && source_alphabet.eq_ignore_ascii_case(target_alphabet))
+        }
+        _ => false,
+    }
+}
@@ -1,9 +1,16 @@
-use multibase::{decode, encode, Base, Base::*};
+use multibase::{decode, decode_to_slice, encode, encode_to_slice, encoded_len, Base, Base::*};
 
 fn encode_decode_assert(input: &[u8], test_cases: Vec<(Base, &str)>) {
     for (base, output) in test_cases {
         assert_eq!(encode(base, input), output);
         assert_eq!(decode(output).unwrap(), (base, input.to_vec()));
+
+        let mut encoded = vec![0; encoded_len(base, input.len()).unwrap()];
+        let len = encode_to_slice(base, input, &mut encoded).unwrap();
+        assert_eq!(&encoded[..len], output.as_bytes());
+        let mut decoded = vec![0; base.decoded_len_max(output.len()).unwrap()];
+        let (_, len) = decode_to_slice(output, &mut decoded).unwrap();
+        assert_eq!(&decoded[..len], input);
     }
 }
 
@@ -65,6 +72,7 @@ fn test_basic() {
         (Base64Pad, "MeWVzIG1hbmkgIQ=="),
         (Base64Url, "ueWVzIG1hbmkgIQ"),
         (Base64UrlPad, "UeWVzIG1hbmkgIQ=="),
+        (Proquint, "pro-lojoj-lasob-kujod-kunon-fabod"),
         (Base256Emoji, "🚀🏃✋🌈😅🌷🤤😻🌟😅👏"),
     ];
     encode_decode_assert(input, test_cases);
@@ -98,6 +106,7 @@ fn preserves_leading_zero() {
         (Base64Pad, "MAHllcyBtYW5pICE="),
         (Base64Url, "uAHllcyBtYW5pICE"),
         (Base64UrlPad, "UAHllcyBtYW5pICE="),
+        (Proquint, "pro-badun-kijug-fadot-kajov-kohob-fad"),
         (Base256Emoji, "🚀🚀🏃✋🌈😅🌷🤤😻🌟😅👏"),
     ];
     encode_decode_assert(input, test_cases);
@@ -131,6 +140,7 @@ fn preserves_two_leading_zeroes() {
         (Base64Pad, "MAAB5ZXMgbWFuaSAh"),
         (Base64Url, "uAAB5ZXMgbWFuaSAh"),
         (Base64UrlPad, "UAAB5ZXMgbWFuaSAh"),
+        (Proquint, "pro-babab-lojoj-lasob-kujod-kunon-fabod"),
         (Base256Emoji, "🚀🚀🚀🏃✋🌈😅🌷🤤😻🌟😅👏"),
     ];
     encode_decode_assert(input, test_cases);
@@ -158,3 +168,1220 @@ fn case_insensitivity() {
         assert_eq!(decode(output).unwrap(), (base, input.to_vec()));
     }
 }
+
+#[test]
+fn stream_encode() {
+    use multibase::Encoder;
+    use std::io::Write;
+
+    let input = b"we all want decentralization";
+    let bases = [
+        Base2,
+        Base8,
+        Base16Lower,
+        Base16Upper,
+        Base32Lower,
+        Base32PadUpper,
+        Base32HexPadLower,
+        Base32Z,
+        Base64,
+        Base64Pad,
+        Base64UrlPad,
+    ];
+    for base in bases {
+        for chunk_size in 1..=input.len() {
+            let mut encoder = Encoder::new(base, Vec::new()).unwrap();
+            for chunk in input.chunks(chunk_size) {
+                encoder.write_all(chunk).unwrap();
+            }
+            let encoded = encoder.finish().unwrap();
+            assert_eq!(String::from_utf8(encoded).unwrap(), encode(base, input));
+        }
+    }
+
+    let encoder = Encoder::new(Base16Lower, Vec::new()).unwrap();
+    assert_eq!(encoder.finish().unwrap(), b"f");
+
+    // A failed `finish` does not write the trailing block again when the encoder is dropped.
+    struct Flaky {
+        written: std::rc::Rc<std::cell::RefCell<Vec<u8>>>,
+        fail_at: Option<usize>,
+    }
+    impl Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let mut written = self.written.borrow_mut();
+            let len = match self.fail_at {
+                Some(at) if written.len() >= at => {
+                    self.fail_at = None;
+                    return Err(std::io::Error::other("flaky"));
+                }
+                Some(at) => buf.len().min(at - written.len()),
+                None => buf.len(),
+            };
+            written.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let written = std::rc::Rc::default();
+    let mut encoder = Encoder::new(
+        Base64,
+        Flaky {
+            written: std::rc::Rc::clone(&written),
+            fail_at: Some(6),
+        },
+    )
+    .unwrap();
+    encoder.write_all(b"hello").unwrap();
+    assert!(encoder.finish().is_err());
+    assert_eq!(*written.borrow(), b"maGVsb");
+
+    for base in [
+        Identity,
+        Base10,
+        Base36Lower,
+        Base45,
+        Base58Btc,
+        Base256Emoji,
+    ] {
+        assert_eq!(
+            Encoder::new(base, Vec::new()).err(),
+            Some(multibase::Error::NotStreamable(base))
+        );
+    }
+}
+
+#[test]
+fn stream_decode() {
+    use multibase::Decoder;
+    use std::io::Read;
+
+    let input = b"we all want decentralization";
+    let bases = [
+        Base2,
+        Base8,
+        Base16Lower,
+        Base16Upper,
+        Base32Lower,
+        Base32PadUpper,
+        Base32HexPadLower,
+        Base32Z,
+        Base64,
+        Base64Pad,
+        Base64UrlPad,
+    ];
+    for base in bases {
+        let encoded = encode(base, input);
+        let mut decoder = Decoder::new(encoded.as_bytes()).unwrap();
+        assert_eq!(decoder.base(), base);
+        let mut decoded = Vec::new();
+        decoder.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, input);
+    }
+
+    let large: Vec<u8> = (0..20_000).map(|i| (i % 251) as u8).collect();
+    for base in [Base8, Base32PadLower, Base64] {
+        let encoded = encode(base, &large);
+        let mut decoded = Vec::new();
+        Decoder::new(encoded.as_bytes())
+            .unwrap()
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, large);
+    }
+
+    let mut decoded = Vec::new();
+    let mut decoder = Decoder::new(&b"bnbswy3dpeB3W64TMMQ"[..]).unwrap();
+    decoder.read_to_end(&mut decoded).unwrap();
+    assert_eq!(decoded, b"hello world");
+
+    let mut decoder = Decoder::new(&b"f68656c6c6"[..]).unwrap();
+    assert!(decoder.read_to_end(&mut Vec::new()).is_err());
+    assert!(Decoder::new(&b"z7paNL19xttacUY"[..]).is_err());
+    assert!(Decoder::new(&b""[..]).is_err());
+    for input in [&b"\xff"[..], b"\xf0\x9f"] {
+        let err = Decoder::new(input).err().unwrap();
+        assert_eq!(
+            err.into_inner().unwrap().downcast_ref(),
+            Some(&multibase::Error::InvalidUtf8 { position: 0 })
+        );
+    }
+}
+
+#[test]
+fn slice_lengths() {
+    let input = [0xab; 32];
+    for base in [
+        Base2,
+        Base16Upper,
+        Base32Lower,
+        Base32PadLower,
+        Base45,
+        Base64Url,
+    ] {
+        let mut output = vec![0; encoded_len(base, input.len()).unwrap()];
+        let len = encode_to_slice(base, input, &mut output).unwrap();
+        assert_eq!(len, output.len());
+        assert_eq!(
+            encode_to_slice(base, input, &mut output[..len - 1]),
+            Err(multibase::Error::BufferTooSmall)
+        );
+    }
+
+    let mut output = [0; 4];
+    assert_eq!(
+        decode_to_slice("z7paNL19xttacUY", &mut output),
+        Err(multibase::Error::BufferTooSmall)
+    );
+    assert_eq!(
+        decode_to_slice("f796573206d616e692021", &mut output),
+        Err(multibase::Error::BufferTooSmall)
+    );
+}
+
+#[test]
+fn decode_errors() {
+    use multibase::Error;
+
+    assert_eq!(decode(""), Err(Error::EmptyInput));
+    assert_eq!(decode("Xabc"), Err(Error::UnknownBase('X')));
+    assert_eq!(
+        decode("z7paNL0xttacUY"),
+        Err(Error::InvalidSymbol {
+            base: Base58Btc,
+            position: 6,
+            char: '0',
+        })
+    );
+    assert_eq!(
+        Base58Btc.decode("7paNL0xttacUY"),
+        Err(Error::InvalidSymbol {
+            base: Base58Btc,
+            position: 5,
+            char: '0',
+        })
+    );
+    assert_eq!(
+        decode("f796573206d61ü6e692021"),
+        Err(Error::InvalidSymbol {
+            base: Base16Lower,
+            position: 13,
+            char: 'ü',
+        })
+    );
+    assert_eq!(
+        decode("🚀🏃✋x"),
+        Err(Error::InvalidSymbol {
+            base: Base256Emoji,
+            position: 11,
+            char: 'x',
+        })
+    );
+    assert_eq!(
+        decode("RRFF.OEB$D5/DZ"),
+        Err(Error::InvalidLength {
+            base: Base45,
+            len: 13,
+        })
+    );
+    // Chunks decoding to more than 2 bytes, or 1 byte for the last 2 symbols.
+    assert_eq!(
+        decode("RFG"),
+        Err(Error::InvalidSymbol {
+            base: Base45,
+            position: 2,
+            char: 'G',
+        })
+    );
+    assert_eq!(
+        decode("R:::"),
+        Err(Error::InvalidSymbol {
+            base: Base45,
+            position: 3,
+            char: ':',
+        })
+    );
+    assert_eq!(
+        decode("f796"),
+        Err(Error::InvalidLength {
+            base: Base16Lower,
+            len: 3,
+        })
+    );
+    assert_eq!(
+        decode("MeWVzIG1hbmkgI==="),
+        Err(Error::InvalidPadding {
+            base: Base64Pad,
+            position: 14,
+        })
+    );
+    assert_eq!(
+        decode("meWVzIG1hbmkgIR"),
+        Err(Error::TrailingBits {
+            base: Base64,
+            position: 14,
+        })
+    );
+}
+
+#[test]
+fn multibase_string() {
+    use multibase::{Error, Multibase, MultibaseStr};
+    use std::collections::HashSet;
+
+    let multibase: Multibase = "bnbswy3dpeB3W64TMMQ".parse().unwrap();
+    assert_eq!(multibase.base(), Base32Lower);
+    assert_eq!(multibase.payload_str(), "nbswy3dpeB3W64TMMQ");
+    assert_eq!(multibase.decode().unwrap(), b"hello world");
+    assert_eq!(multibase.to_string(), "bnbswy3dpeB3W64TMMQ");
+    assert_eq!(
+        Multibase::encode(Base32Lower, "hello world").as_str(),
+        "bnbswy3dpeb3w64tmmq"
+    );
+
+    let borrowed = MultibaseStr::new("zCn8eVZg").unwrap();
+    assert_eq!(borrowed.base(), Base58Btc);
+    assert_eq!(borrowed.decode().unwrap(), b"hello");
+    assert_eq!(borrowed, borrowed.to_multibase());
+
+    let set: HashSet<Multibase> = ["zCn8eVZg", "f68656c6c6f", "zCn8eVZg"]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    assert_eq!(set.len(), 2);
+
+    assert_eq!("".parse::<Multibase>(), Err(Error::EmptyInput));
+    assert_eq!("Xabc".parse::<Multibase>(), Err(Error::UnknownBase('X')));
+    assert_eq!(
+        MultibaseStr::new("zCn8eVZ0"),
+        Err(Error::InvalidSymbol {
+            base: Base58Btc,
+            position: 7,
+            char: '0',
+        })
+    );
+    let mut long = encode(Base64, [0; 90]);
+    long.replace_range(81..82, "*");
+    assert_eq!(
+        MultibaseStr::new(&long),
+        Err(Error::InvalidSymbol {
+            base: Base64,
+            position: 81,
+            char: '*',
+        })
+    );
+    assert_eq!(
+        MultibaseStr::new("MeWVzIG1hbmkgIQ"),
+        Err(Error::InvalidLength {
+            base: Base64Pad,
+            len: 14,
+        })
+    );
+}
+
+#[test]
+fn base_names() {
+    assert_eq!(Base36Lower.name(), "base36");
+    assert_eq!(Base16Lower.to_string(), "base16");
+    assert_eq!("base58btc".parse::<Base>(), Ok(Base58Btc));
+    assert_eq!(Base::from_name("base36lower"), Ok(Base36Lower));
+    assert_eq!(Base::from_name("base32hexpadlower"), Ok(Base32HexPadLower));
+    let unknown_name = |name: &str| match Base::from_name(name) {
+        Err(multibase::Error::UnknownName(name)) => name.to_string(),
+        result => panic!("{:?}", result),
+    };
+    assert_eq!(unknown_name("base42"), "base42");
+    // Truncated to 32 bytes, at a char boundary.
+    assert_eq!(unknown_name(&"x".repeat(40)), "x".repeat(32));
+    assert_eq!(
+        unknown_name(&format!("{}é", "x".repeat(31))),
+        "x".repeat(31)
+    );
+    assert_eq!(
+        "base42".parse::<Base>().unwrap_err().to_string(),
+        "Unknown base name: base42"
+    );
+
+    for base in Base::iter() {
+        assert_eq!(base.name().parse::<Base>(), Ok(base));
+    }
+}
+
+#[test]
+fn base_metadata() {
+    use multibase::Family;
+
+    assert_eq!(Base::ALL.len(), 26);
+    assert_eq!(Base::ALL[0], Identity);
+    assert_eq!(Base58Btc.alphabet().unwrap().len(), 58);
+    assert_eq!(Base64Pad.block_size(), Some((3, 4)));
+    assert_eq!(Base32Lower.block_size(), Some((5, 8)));
+    assert_eq!(Base8.block_size(), Some((3, 8)));
+    assert_eq!(Base10.block_size(), None);
+    assert_eq!(Base256Emoji.block_size(), None);
+    assert_eq!(Base36Upper.family(), Family::BaseX);
+
+    let input: Vec<u8> = (0..128).collect();
+    for base in Base::iter() {
+        let encoded = base.encode(&input);
+        if let Some(alphabet) = base.alphabet() {
+            assert_eq!(alphabet.chars().count(), base.radix() as usize, "{}", base);
+            assert!(
+                encoded
+                    .chars()
+                    .all(|c| alphabet.contains(c) || Some(c) == base.padding()),
+                "{}",
+                base
+            );
+            assert_eq!(
+                encoded.contains('='),
+                base.padding() == Some('='),
+                "{}",
+                base
+            );
+        }
+
+        let swapped: String = encoded
+            .chars()
+            .map(|c| match c {
+                'a'..='z' => c.to_ascii_uppercase(),
+                'A'..='Z' => c.to_ascii_lowercase(),
+                c => c,
+            })
+            .collect();
+        if swapped != encoded {
+            assert_eq!(
+                base.decode(&swapped).ok().as_ref() == Some(&input),
+                base.is_case_insensitive(),
+                "{}",
+                base
+            );
+        }
+
+        if let Some((bytes, symbols)) = base.block_size() {
+            assert_eq!(
+                base.encode(&input[..bytes]).chars().count(),
+                symbols,
+                "{}",
+                base
+            );
+        }
+    }
+}
+
+#[test]
+fn base_status() {
+    use multibase::{decode_with_status, Status};
+
+    assert_eq!(Base58Btc.status(), Status::Final);
+    assert_eq!(Base45.status(), Status::Draft);
+    assert_eq!(Base256Emoji.status(), Status::Experimental);
+    let finals: Vec<_> = Base::iter()
+        .filter(|base| base.status() == Status::Final)
+        .collect();
+    assert!(finals.contains(&Base64Url) && !finals.contains(&Base64Pad));
+
+    assert!(decode_with_status("MaGVsbG8=", Status::Experimental).is_ok());
+    assert_eq!(
+        decode_with_status("MaGVsbG8=", Status::Draft),
+        Err(multibase::Error::RejectedStatus {
+            base: Base64Pad,
+            status: Status::Experimental,
+        })
+    );
+    assert_eq!(
+        decode_with_status("", Status::Final),
+        Err(multibase::Error::EmptyInput)
+    );
+}
+
+#[test]
+fn decode_options() {
+    use multibase::{DecodeOptions, Error, Padding};
+
+    let hello = b"hello".to_vec();
+    let default = DecodeOptions::default();
+    let strict = DecodeOptions::strict();
+    let lenient = DecodeOptions::lenient();
+
+    // Case folding
+    assert_eq!(
+        default.decode("bNBSWY3DP").unwrap(),
+        (Base32Lower, hello.clone())
+    );
+    assert_eq!(
+        strict.decode("bNBSWY3DP"),
+        Err(Error::InvalidSymbol {
+            base: Base32Lower,
+            position: 1,
+            char: 'N',
+        })
+    );
+    assert!(strict.decode("RRFF.OEB$D").is_ok());
+    assert!(strict.decode("Rrff.oeb$d").is_err());
+
+    // Padding
+    assert!(default.decode("MaGVsbG8").is_err());
+    assert_eq!(
+        lenient.decode("MaGVsbG8").unwrap(),
+        (Base64Pad, hello.clone())
+    );
+    assert_eq!(
+        lenient.decode("MaGVsbG8=").unwrap(),
+        (Base64Pad, hello.clone())
+    );
+    let forbidden = DecodeOptions::new().padding(Padding::Forbidden);
+    assert_eq!(
+        forbidden.decode("MaGVsbG8").unwrap(),
+        (Base64Pad, hello.clone())
+    );
+    assert_eq!(
+        forbidden.decode("MaGVsbG8="),
+        Err(Error::InvalidPadding {
+            base: Base64Pad,
+            position: 8,
+        })
+    );
+    assert_eq!(
+        lenient.decode("MaGVsb"),
+        Err(Error::InvalidLength {
+            base: Base64Pad,
+            len: 5,
+        })
+    );
+
+    // Whitespace
+    assert!(default.decode(" zCn8eVZg").is_err());
+    assert_eq!(
+        lenient.decode("  zCn8e\r\nVZg\n").unwrap(),
+        (Base58Btc, hello.clone())
+    );
+    // The space is a Base45 symbol.
+    assert_eq!(lenient.decode("R 00").unwrap(), (Base45, vec![0, 36]));
+    assert_eq!(
+        lenient.decode(" R 00 00\n").unwrap(),
+        (Base45, vec![0, 36, 0, 36])
+    );
+    assert_eq!(
+        lenient.decode("R00 "),
+        Err(Error::InvalidSymbol {
+            base: Base45,
+            position: 3,
+            char: ' ',
+        })
+    );
+    assert_eq!(
+        lenient.decode(" z0"),
+        Err(Error::InvalidSymbol {
+            base: Base58Btc,
+            position: 2,
+            char: '0',
+        })
+    );
+
+    // Trailing bits
+    assert_eq!(
+        default.decode("maGVsbG9"),
+        Err(Error::TrailingBits {
+            base: Base64,
+            position: 7,
+        })
+    );
+    assert_eq!(lenient.decode("maGVsbG9").unwrap(), (Base64, hello.clone()));
+    assert_eq!(
+        Base64.decode_with_options("aGVsbG9", &lenient).unwrap(),
+        hello
+    );
+}
+
+#[test]
+fn canonical() {
+    use multibase::{decode_canonical, Error};
+
+    assert_eq!(
+        decode_canonical("zCn8eVZg").unwrap(),
+        (Base58Btc, b"hello".to_vec())
+    );
+    for base in Base::iter() {
+        assert!(base.is_canonical(base.encode(b"hello")), "{}", base);
+    }
+
+    // Other case of case-insensitive bases
+    assert!(!Base36Lower.is_canonical("5PZCSZU7"));
+    assert!(!Base32Upper.is_canonical("nbswy3dp"));
+    assert!(!Base45.is_canonical("rrff.oeb$d"));
+    assert_eq!(
+        decode_canonical("k5pzCszu7"),
+        Err(Error::NonCanonical {
+            base: Base36Lower,
+            position: 4,
+        })
+    );
+    // Non-zero trailing bits, not decodable at all
+    assert!(!Base64.is_canonical("aGVsbG9"));
+    // Padding on an unpadded base
+    assert!(!Base64.is_canonical("aGVsbG8="));
+    assert_eq!(decode_canonical(""), Err(Error::EmptyInput));
+}
+
+#[test]
+fn guess_base() {
+    use multibase::{guess, Confidence};
+
+    assert!(guess("").is_empty());
+    assert_eq!(guess("DEADBEEF")[0], (Base16Upper, Confidence::High));
+    assert!(!guess("DEADBEEF")
+        .iter()
+        .any(|&(base, _)| base == Base16Lower));
+    // Ties are ranked by spec status
+    assert_eq!(
+        guess("aGVsbG8="),
+        vec![
+            (Base64UrlPad, Confidence::High),
+            (Base64Pad, Confidence::High)
+        ]
+    );
+    assert_eq!(guess("RFF.OEB$D"), vec![(Base45, Confidence::High)]);
+    assert_eq!(guess("0110100001101001")[0], (Base2, Confidence::High));
+
+    let key = &encode(Base58Btc, [7; 32])[1..];
+    let guesses = guess(key);
+    assert_eq!(guesses[0], (Base58Btc, Confidence::High));
+    assert_eq!(guesses[1], (Base58Flickr, Confidence::High));
+    assert!(guesses[2..]
+        .iter()
+        .all(|&(_, confidence)| confidence < Confidence::High));
+}
+
+#[test]
+fn proquint() {
+    use multibase::Error;
+
+    // Vectors of the proquint paper, IPv4 addresses
+    encode_decode_assert(&[127, 0, 0, 1], vec![(Proquint, "pro-lusab-babad")]);
+    encode_decode_assert(&[63, 84, 220, 193], vec![(Proquint, "pro-gutih-tugad")]);
+    encode_decode_assert(&[63, 118, 7, 35], vec![(Proquint, "pro-gutuk-bisog")]);
+    encode_decode_assert(&[140, 98, 193, 141], vec![(Proquint, "pro-mudof-sakat")]);
+    // Odd length, the last byte is a 3 symbol word
+    encode_decode_assert(&[127], vec![(Proquint, "pro-lug")]);
+    encode_decode_assert(&[127, 0, 0], vec![(Proquint, "pro-lusab-bab")]);
+    encode_decode_assert(&[], vec![(Proquint, "pro-")]);
+
+    let invalid = |position, char| {
+        Err(Error::InvalidSymbol {
+            base: Proquint,
+            position,
+            char,
+        })
+    };
+    assert_eq!(decode("pra-lusab"), invalid(2, 'a'));
+    assert_eq!(decode("pro-lusab-"), invalid(9, '-'));
+    assert_eq!(decode("pro-lusab--babad"), invalid(10, '-'));
+    assert_eq!(decode("pro-lusabbabad"), invalid(9, 'b'));
+    assert_eq!(decode("pro-lub-babad"), invalid(7, '-'));
+    assert_eq!(decode("pro-lxsab"), invalid(5, 'x'));
+    assert_eq!(decode("pro-lus"), invalid(6, 's'));
+    assert_eq!(decode("pro-lusab-€"), invalid(10, '€'));
+    assert_eq!(
+        decode("pro-lusa"),
+        Err(Error::InvalidLength {
+            base: Proquint,
+            len: 7,
+        })
+    );
+    assert_eq!(
+        decode("pr"),
+        Err(Error::InvalidLength {
+            base: Proquint,
+            len: 1,
+        })
+    );
+}
+
+#[test]
+fn reserved_prefix() {
+    use multibase::{decode_with_legacy, Error};
+
+    assert_eq!(Base::from_code('1'), Err(Error::ReservedPrefix('1')));
+    assert_eq!(decode("/ip4/127.0.0.1"), Err(Error::ReservedPrefix('/')));
+    assert_eq!(decode("Xabc"), Err(Error::UnknownBase('X')));
+
+    let multihash = [&[0x12, 0x20][..], &[0xab; 32]].concat();
+    let cid_v0 = &encode(Base58Btc, &multihash)[1..];
+    assert!(cid_v0.starts_with("Qm"));
+    assert_eq!(decode(cid_v0), Err(Error::ReservedPrefix('Q')));
+    assert_eq!(decode_with_legacy(cid_v0).unwrap(), (Base58Btc, multihash));
+    assert_eq!(decode_with_legacy("12").unwrap(), (Base58Btc, vec![0, 1]));
+    assert_eq!(
+        decode_with_legacy("zCn8eVZg").unwrap(),
+        (Base58Btc, b"hello".to_vec())
+    );
+    assert_eq!(decode_with_legacy("/ip4"), Err(Error::ReservedPrefix('/')));
+    assert_eq!(
+        decode_with_legacy("Qm0"),
+        Err(Error::InvalidSymbol {
+            base: Base58Btc,
+            position: 2,
+            char: '0',
+        })
+    );
+}
+
+#[test]
+fn binary_identity() {
+    use multibase::{decode_bytes, encode_bytes, try_encode, Error};
+
+    let binary = [0xff, 0x00, 0xc3];
+    assert_eq!(encode_bytes(Identity, binary), [0x00, 0xff, 0x00, 0xc3]);
+    assert_eq!(
+        decode_bytes(encode_bytes(Identity, binary)).unwrap(),
+        (Identity, binary.to_vec())
+    );
+    assert_eq!(
+        try_encode(Identity, binary),
+        Err(Error::InvalidUtf8 { position: 0 })
+    );
+    assert_eq!(try_encode(Base64, binary).unwrap(), "m/wDD");
+    assert_eq!(
+        Identity.try_encode(binary),
+        Err(Error::InvalidUtf8 { position: 0 })
+    );
+    assert_eq!(Base64.try_encode(binary).unwrap(), "/wDD");
+
+    for base in Base::iter() {
+        let encoded = encode_bytes(base, b"hello");
+        assert_eq!(encoded, encode(base, b"hello").into_bytes());
+        assert_eq!(decode_bytes(&encoded).unwrap(), (base, b"hello".to_vec()));
+    }
+
+    assert_eq!(decode_bytes([]), Err(Error::EmptyInput));
+    assert_eq!(
+        decode_bytes(b"zCn8\xffVZg"),
+        Err(Error::InvalidSymbol {
+            base: Base58Btc,
+            position: 4,
+            char: char::REPLACEMENT_CHARACTER,
+        })
+    );
+    assert_eq!(
+        decode_bytes([0xff, b'a']),
+        Err(Error::InvalidUtf8 { position: 0 })
+    );
+}
+
+#[test]
+fn registry() {
+    use multibase::{decode_with, Codec, Error, Registry, Result};
+
+    /// Base16 with the symbols shifted to `g..v`.
+    struct Shifted;
+
+    impl Codec for Shifted {
+        fn encode(&self, input: &[u8]) -> String {
+            Base16Lower
+                .encode(input)
+                .chars()
+                .map(|c| (b'g' + c.to_digit(16).unwrap() as u8) as char)
+                .collect()
+        }
+
+        fn decode(&self, input: &str) -> Result<Vec<u8>> {
+            let hex = input
+                .chars()
+                .map(|c| match c {
+                    'g'..='v' => Ok(std::char::from_digit(c as u32 - 'g' as u32, 16).unwrap()),
+                    _ => Err(Error::InvalidBaseString),
+                })
+                .collect::<Result<String>>()?;
+            Base16Lower.decode(hex)
+        }
+    }
+
+    let mut registry = Registry::new();
+    assert!(registry.codes().next().is_none());
+    registry.register('x', Shifted).unwrap();
+    assert_eq!(registry.register('x', Shifted), Err(Error::CodeInUse('x')));
+    assert_eq!(registry.register('f', Shifted), Err(Error::CodeInUse('f')));
+    assert_eq!(
+        registry.register('Q', Shifted),
+        Err(Error::ReservedPrefix('Q'))
+    );
+    assert_eq!(registry.codes().collect::<Vec<_>>(), vec!['x']);
+
+    assert_eq!(registry.encode('x', b"hi").unwrap(), "xmomp");
+    assert_eq!(registry.encode('z', b"hello").unwrap(), "zCn8eVZg");
+    assert_eq!(registry.encode('X', b"hi"), Err(Error::UnknownBase('X')));
+    assert_eq!(
+        decode_with(&registry, "xmomp").unwrap(),
+        ('x', b"hi".to_vec())
+    );
+    assert_eq!(
+        decode_with(&registry, "f6869").unwrap(),
+        ('f', b"hi".to_vec())
+    );
+    assert_eq!(decode_with(&registry, "xm0"), Err(Error::InvalidBaseString));
+    assert_eq!(decode("xmomp"), Err(Error::UnknownBase('x')));
+}
+
+#[test]
+fn typed_multibase() {
+    use multibase::bases::{Base32Lower, Base58Btc, Base64Pad};
+    use multibase::{Error, Multibase, TypedBase, TypedMultibase};
+    use std::convert::TryFrom;
+
+    assert_eq!(Base32Lower::BASE, Base::Base32Lower);
+    assert_eq!(Base32Lower::CODE, 'b');
+    assert_eq!(Base32Lower::NAME, "base32");
+    assert_eq!(Base32Lower::ALPHABET, Base::Base32Lower.alphabet());
+    assert_eq!(Base64Pad::CODE, Base::Base64Pad.code());
+
+    let typed = TypedMultibase::<Base58Btc>::encode(b"hello");
+    assert_eq!(typed.as_str(), "zCn8eVZg");
+    assert_eq!(typed.payload_str(), "Cn8eVZg");
+    assert_eq!(typed.base(), Base::Base58Btc);
+    assert_eq!(typed.decode().unwrap(), b"hello");
+    assert_eq!(
+        "zCn8eVZg".parse::<TypedMultibase<Base58Btc>>(),
+        Ok(typed.clone())
+    );
+
+    let multibase: Multibase = "bnbswy3dp".parse().unwrap();
+    assert_eq!(
+        TypedMultibase::<Base58Btc>::try_from(multibase.clone()),
+        Err(Error::WrongBase {
+            expected: Base::Base58Btc,
+            found: Base::Base32Lower
+        })
+    );
+    let typed = TypedMultibase::<Base32Lower>::try_from(multibase.clone()).unwrap();
+    assert_eq!(typed, multibase);
+    assert_eq!(Multibase::from(typed), multibase);
+
+    assert_eq!(
+        TypedMultibase::<Base32Lower>::try_from(String::from("b01")),
+        Err(Error::InvalidSymbol {
+            base: Base::Base32Lower,
+            position: 1,
+            char: '0'
+        })
+    );
+    assert_eq!(
+        "zCn8eV"
+            .parse::<TypedMultibase<Base58Btc>>()
+            .unwrap()
+            .decode(),
+        Base::Base58Btc.decode("Cn8eV")
+    );
+}
+
+#[test]
+fn literal() {
+    use multibase::multibase;
+
+    const IDENTITY: [u8; 5] = multibase!("\0hello");
+    const BASE2: [u8; 1] = multibase!("001101000");
+    const BASE16: [u8; 5] = multibase!("F68656c6C6F");
+    const BASE32_PAD: [u8; 5] = multibase!("cnbswy3dp");
+    const BASE36: [u8; 5] = multibase!("k5pzcszu7");
+    const BASE45: [u8; 6] = multibase!("RRFF.OEB$D");
+    const BASE58: [u8; 7] = multibase!("z11Cn8eVZg");
+    const BASE64_PAD: [u8; 5] = multibase!("MaGVsbG8=");
+    const EMOJI: [u8; 2] = multibase!("🚀🚀🪐");
+    const PROQUINT: [u8; 3] = multibase!("pro-lusab-bab");
+    const EMPTY: [u8; 0] = multibase!("z");
+
+    assert_eq!(&IDENTITY, b"hello");
+    assert_eq!(&BASE16, b"hello");
+    assert_eq!(&BASE36, b"hello");
+    assert_eq!(PROQUINT, [127, 0, 0]);
+    assert_eq!(&BASE58, b"\0\0hello");
+    assert_eq!(&BASE64_PAD, b"hello");
+    assert_eq!(EMPTY, []);
+
+    let cases: [(&str, &[u8]); 5] = [
+        ("001101000", &BASE2),
+        ("cnbswy3dp", &BASE32_PAD),
+        ("🚀🚀🪐", &EMOJI),
+        ("pro-lusab-bab", &PROQUINT),
+        ("RRFF.OEB$D", &BASE45),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(decode(input).unwrap().1, expected, "{}", input);
+    }
+}
+
+#[test]
+fn literal_matches_runtime() {
+    use multibase::multibase;
+
+    macro_rules! decoded_bases {
+        ($($input:literal,)*) => {{
+            let mut bases = Vec::new();
+            $(
+                let (base, decoded) = decode($input).unwrap();
+                assert_eq!(&multibase!($input)[..], &decoded[..], "{}", $input);
+                bases.push(base);
+            )*
+            bases
+        }};
+    }
+
+    // Every base, with leading zeros and mixed case where the base allows it.
+    let bases = decoded_bases! {
+        "\0\0\0hello world",
+        "00000000000000000011010000110010101101100011011000110111100100000011101110110111101110010011011000110010011111111",
+        "700000150312661543362016733671154311774",
+        "90032309054545037006034346730751",
+        "f000068656C6c6f20776f726c64ff",
+        "F000068656C6C6F20776F726C64FF",
+        "baaagqzlmnrxsa53pojwgj7y",
+        "BAAAGQZLMnrxsa53POJWGJ7Y",
+        "caaagqzlmnrxsa53pojwgj7y=",
+        "CAAAGQZLMNRXSA53POJWGJ7Y=",
+        "v0006gpbcdhni0trfe9m69vo",
+        "V0006GPBCDHNI0TRFE9M69VO",
+        "t0006gpbcdhni0trfe9m69vo=",
+        "T0006GPBCDHNI0TRFE9M69VO=",
+        "hyyygo3mcptz1y75xqjsgj9a",
+        "k0034rlxmuap2o2a5hn01r",
+        "K0034RLXMUAP2o2a5hn01r",
+        "R000+8d VD82EK4F.KEPYC",
+        "Z112YgeAWqfYGoyyBKnZ",
+        "z112yGEbwRFyhPZZckNz",
+        "mAABoZWxsbyB3b3JsZP8",
+        "MAABoZWxsbyB3b3JsZP8=",
+        "uAABoZWxsbyB3b3JsZP8",
+        "UAABoZWxsbyB3b3JsZP8=",
+        "pro-babab-kodoj-kudos-kusob-litoz-lanos-kiguz",
+        "🚀🚀🚀😴✋🍀🍀😓😅✔😓🥺🍀😳🥂",
+    };
+    assert!(Base::iter().all(|base| bases.contains(&base)));
+}
+
+#[test]
+fn constant_time() {
+    use multibase::{ct_eq, Error, Family};
+
+    let data: Vec<u8> = (0..64u8).map(|i| i.wrapping_mul(151)).collect();
+    for base in Base::iter() {
+        if base.family() != Family::Rfc4648 {
+            assert_eq!(base.encode_ct(&data), Err(Error::Unsupported(base)));
+            assert_eq!(base.decode_ct("00"), Err(Error::Unsupported(base)));
+            continue;
+        }
+        for len in 0..data.len() {
+            let encoded = base.encode(&data[..len]);
+            assert_eq!(base.encode_ct(&data[..len]).unwrap(), encoded, "{:?}", base);
+            let decoded = base.decode_ct(&encoded).unwrap();
+            assert!(ct_eq(&decoded, &data[..len]), "{:?}", base);
+            for input in [
+                encoded.to_lowercase(),
+                encoded.to_uppercase(),
+                format!("{}=", encoded),
+                format!("{}!", encoded),
+                encoded[..encoded.len().saturating_sub(1)].to_owned(),
+            ] {
+                assert_eq!(base.decode_ct(&input), base.decode(&input), "{:?}", base);
+            }
+        }
+    }
+    assert_eq!(
+        Base::Base64.decode_ct("aGVsbG9"),
+        Err(Error::TrailingBits {
+            base: Base::Base64,
+            position: 6
+        })
+    );
+}
+
+#[test]
+fn radix_conversion() {
+    /// The quadratic conversion, one symbol at a time.
+    fn reference(alphabet: &[u8], input: &[u8]) -> String {
+        let radix = alphabet.len() as u32;
+        let zeros = input.iter().take_while(|&&byte| byte == 0).count();
+        let mut digits = Vec::new();
+        for &byte in &input[zeros..] {
+            let mut carry = u32::from(byte);
+            for digit in digits.iter_mut() {
+                carry += *digit << 8;
+                *digit = carry % radix;
+                carry /= radix;
+            }
+            while carry > 0 {
+                digits.push(carry % radix);
+                carry /= radix;
+            }
+        }
+        let mut output = vec![alphabet[0]; zeros];
+        output.extend(digits.iter().rev().map(|&digit| alphabet[digit as usize]));
+        String::from_utf8(output).unwrap()
+    }
+
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut random = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as u8
+    };
+    let sizes = (0..40).chain([255, 256, 257, 520, 1024, 4097]);
+    for size in sizes {
+        let random: Vec<u8> = (0..size).map(|_| random()).collect();
+        let ones = vec![0xff; size];
+        let mut zeros = vec![0; size];
+        if let Some(last) = zeros.last_mut() {
+            *last = 1;
+        }
+        for input in [random, ones, zeros] {
+            for base in [Base10, Base36Lower, Base58Btc] {
+                let alphabet = base.alphabet().unwrap().as_bytes();
+                let encoded = base.encode(&input);
+                assert_eq!(encoded, reference(alphabet, &input), "{:?} {}", base, size);
+                assert_eq!(base.decode(&encoded).unwrap(), input, "{:?} {}", base, size);
+
+                let mut output = vec![0; base.encoded_len(size).unwrap()];
+                let len = base.encode_to_slice(&input, &mut output).unwrap();
+                assert_eq!(&output[..len], encoded.as_bytes());
+                let mut output = vec![0; base.decoded_len_max(encoded.len()).unwrap()];
+                let len = base.decode_to_slice(&encoded, &mut output).unwrap();
+                assert_eq!(&output[..len], &input[..]);
+            }
+        }
+    }
+}
+
+#[test]
+fn decode_limits() {
+    use multibase::{DecodeLimits, DecodeOptions, Error};
+
+    let options = DecodeOptions::new().limits(DecodeLimits::new(9, 7));
+    assert_eq!(
+        options.decode("zCn8eVZg").unwrap(),
+        (Base58Btc, b"hello".to_vec())
+    );
+    assert_eq!(
+        options.decode("f68656c6c6f").unwrap_err(),
+        Error::InputTooLong { len: 11, max: 9 }
+    );
+    assert_eq!(
+        options.decode("\0abcdefgh"),
+        Err(Error::OutputTooLong { len: 8, max: 7 })
+    );
+    assert_eq!(
+        Base58Btc.decode_with_options("1111111111", &options),
+        Err(Error::InputTooLong { len: 10, max: 9 })
+    );
+    assert_eq!(
+        Base16Lower.decode_with_options("68656c6c6f", &options),
+        Err(Error::InputTooLong { len: 10, max: 9 })
+    );
+    assert_eq!(
+        Base16Lower
+            .decode_with_options("68656c6c", &options)
+            .unwrap(),
+        b"hell"
+    );
+
+    let lenient = DecodeOptions::lenient().limits(DecodeLimits::new(usize::MAX, 4));
+    assert_eq!(
+        lenient.decode(" f68656c6c6f "),
+        Err(Error::OutputTooLong { len: 5, max: 4 })
+    );
+    assert_eq!(DecodeLimits::default(), DecodeLimits::UNLIMITED);
+    assert_eq!(
+        DecodeOptions::new().limits(DecodeLimits::UNLIMITED),
+        DecodeOptions::default()
+    );
+
+    let multibase: multibase::Multibase = "f68656c6c6f".parse().unwrap();
+    assert_eq!(
+        multibase.decode_with_options(&lenient),
+        Err(Error::OutputTooLong { len: 5, max: 4 })
+    );
+}
+
+#[test]
+fn stream_limits() {
+    use multibase::{DecodeLimits, Decoder, Error};
+    use std::io::Read;
+
+    let encoded = encode(Base64, [0x5a; 20_000]);
+    let read = |limits| {
+        let mut decoder = Decoder::with_limits(encoded.as_bytes(), limits).unwrap();
+        let mut decoded = Vec::new();
+        decoder.read_to_end(&mut decoded).map(|_| decoded.len())
+    };
+    let error = |limits| {
+        let err = read(limits).unwrap_err().into_inner().unwrap();
+        err.downcast_ref::<Error>().cloned()
+    };
+    assert_eq!(read(DecodeLimits::new(26_668, 20_000)).unwrap(), 20_000);
+    // The input is read and decoded in chunks of 8 KiB.
+    assert_eq!(
+        error(DecodeLimits::new(20_000, usize::MAX)),
+        Some(Error::InputTooLong {
+            len: 24_577,
+            max: 20_000,
+        })
+    );
+    assert_eq!(
+        error(DecodeLimits::new(usize::MAX, 10_000)),
+        Some(Error::OutputTooLong {
+            len: 12_288,
+            max: 10_000,
+        })
+    );
+}
+
+#[test]
+fn fixed_capacity() {
+    use multibase::{decode_to_array, encode_to_array, ArrayBytes, ArrayString, Error};
+
+    for base in Base::iter() {
+        let input = b"yes mani !";
+        let encoded: ArrayString<96> = encode_to_array(base, input).unwrap();
+        assert_eq!(encoded.as_str(), encode(base, input));
+        assert_eq!(encoded.capacity(), 96);
+
+        let (decoded_base, decoded) = decode_to_array::<16, _>(&*encoded).unwrap();
+        assert_eq!(decoded_base, base);
+        assert_eq!(decoded, *input);
+        assert_eq!(decoded.capacity(), 16);
+    }
+
+    let encoded: ArrayString<4> = encode_to_array(Base16Lower, b"a").unwrap();
+    assert_eq!(encoded.as_str(), "f61");
+    assert_eq!(encoded.to_string(), "f61");
+    assert_eq!(format!("{:?}", encoded), "\"f61\"");
+    assert_eq!(
+        encode_to_array::<3, _>(Base16Lower, b"ab"),
+        Err(Error::BufferTooSmall)
+    );
+    assert_eq!(
+        encode_to_array::<4, _>(Identity, [0x68, 0xff]),
+        Err(Error::InvalidUtf8 { position: 1 })
+    );
+
+    let (_, decoded): (_, ArrayBytes<4>) = decode_to_array("R+8D VD").unwrap();
+    assert_eq!(decoded.as_slice(), b"hell");
+    assert_eq!(format!("{:?}", decoded), "[104, 101, 108, 108]");
+    assert_eq!(
+        decode_to_array::<3, _>("R+8D VD"),
+        Err(Error::BufferTooSmall)
+    );
+    assert_eq!(
+        decode_to_array::<4, _>("R:::"),
+        Err(Error::InvalidSymbol {
+            base: Base45,
+            position: 3,
+            char: ':',
+        })
+    );
+}
+
+#[test]
+fn supported_bases() {
+    // Every family of bases is enabled by the default features.
+    assert!(Base::iter().all(|base| base.is_supported()));
+}
+
+#[test]
+fn transcode() {
+    use multibase::{transcode, Error};
+
+    for input in [&b""[..], b"y", b"ye", b"yes", b"yes mani !", &[0, 0, 0xff]] {
+        for source in Base::iter().filter(|&base| base != Identity) {
+            let encoded = encode(source, input);
+            for target in Base::iter().filter(|&base| base != Identity) {
+                assert_eq!(
+                    transcode(&encoded, target).unwrap(),
+                    encode(target, input),
+                    "{:?} to {:?}",
+                    source,
+                    target
+                );
+            }
+        }
+    }
+
+    assert_eq!(transcode("bAFYbeia", Base32Lower).unwrap(), "bafybeia");
+    assert_eq!(transcode("C7A======", Base32Lower).unwrap(), "b7a");
+    assert_eq!(transcode("Mf/8=", Base64).unwrap(), "mf/8");
+    assert_eq!(
+        transcode("F68656C6C6F", Base16Lower).unwrap(),
+        "f68656c6c6f"
+    );
+    assert_eq!(
+        transcode("K2DPW0B0HTGC", Base36Lower).unwrap(),
+        "k2dpw0b0htgc"
+    );
+    assert_eq!(transcode("\0hello", Base16Lower).unwrap(), "f68656c6c6f");
+    assert_eq!(
+        transcode("f68ff", Identity),
+        Err(Error::InvalidUtf8 { position: 1 })
+    );
+
+    assert_eq!(transcode("", Base32Lower), Err(Error::EmptyInput));
+    assert_eq!(
+        transcode("b68", Base32Upper),
+        Err(Error::InvalidSymbol {
+            base: Base32Lower,
+            position: 2,
+            char: '8',
+        })
+    );
+    assert_eq!(
+        transcode("C7A=====", Base32Lower),
+        Err(Error::InvalidLength {
+            base: Base32PadUpper,
+            len: 7,
+        })
+    );
+    assert_eq!(
+        transcode("z0", Base58Btc),
+        Err(Error::InvalidSymbol {
+            base: Base58Btc,
+            position: 1,
+            char: '0',
+        })
+    );
+
+    // The symbol by symbol path rejects what the decoder rejects, with the same error.
+    for input in [
+        "bab",
+        "ba",
+        "bab=",
+        "cab=====",
+        "cabc=====",
+        "c========",
+        "ca=b=====",
+        "m/x",
+        "m/w=",
+        "M/w=",
+        "M/w",
+        "M/x==",
+        "fa",
+        "fag",
+        "0101",
+        "0101010a",
+        "7777",
+        "BAB",
+        "kz!",
+    ] {
+        let error = decode(input).err();
+        assert!(error.is_some(), "{}", input);
+        let base = Base::from_code(input.chars().next().unwrap()).unwrap();
+        for target in Base::iter().filter(|&target| target.family() == base.family()) {
+            assert_eq!(
+                transcode(input, target).err(),
+                error,
+                "{} to {:?}",
+                input,
+                target
+            );
+        }
+    }
+}
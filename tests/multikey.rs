@@ -0,0 +1,126 @@
+use multibase::multikey::{self, Error, KeyType};
+use multibase::Base;
+
+#[test]
+fn multikey_round_trip() {
+    let prefixes = [
+        (KeyType::Ed25519, "z6Mk"),
+        (KeyType::X25519, "z6LS"),
+        (KeyType::Secp256k1, "zQ3s"),
+        (KeyType::P256, "zDn"),
+    ];
+    for (key_type, prefix) in prefixes {
+        let mut key = vec![0x5a; key_type.key_len()];
+        key[0] = 0x02;
+
+        let encoded = multikey::encode(key_type, &key).unwrap();
+        assert!(encoded.starts_with(prefix), "{}: {}", key_type, encoded);
+        assert_eq!(multikey::decode(&encoded).unwrap(), (key_type, key.clone()));
+
+        let did = multikey::encode_did_key(key_type, &key).unwrap();
+        assert_eq!(did, format!("did:key:{}", encoded));
+        assert_eq!(multikey::decode(&did).unwrap(), (key_type, key));
+    }
+}
+
+#[test]
+fn multikey_spec_vectors() {
+    let vectors = [
+        (
+            "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+            KeyType::Ed25519,
+        ),
+        (
+            "did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F",
+            KeyType::X25519,
+        ),
+        (
+            "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme",
+            KeyType::Secp256k1,
+        ),
+        (
+            "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169",
+            KeyType::P256,
+        ),
+    ];
+    for (did, key_type) in vectors {
+        let (decoded_type, key) = multikey::decode(did).unwrap();
+        assert_eq!(decoded_type, key_type);
+        assert_eq!(key.len(), key_type.key_len());
+        assert_eq!(multikey::encode_did_key(key_type, &key).unwrap(), did);
+    }
+}
+
+#[test]
+fn multikey_errors() {
+    let empty = Error::Multibase(multibase::Error::EmptyInput);
+    assert_eq!(multikey::decode(""), Err(empty.clone()));
+    assert_eq!(multikey::decode("did:key:"), Err(empty));
+    assert_eq!(
+        multikey::decode("f ed01"),
+        Err(Error::Multibase(multibase::Error::WrongBase {
+            expected: Base::Base58Btc,
+            found: Base::Base16Lower,
+        }))
+    );
+    let invalid_symbol = multibase::Error::InvalidSymbol {
+        base: Base::Base58Btc,
+        position: 12,
+        char: '0',
+    };
+    assert_eq!(
+        multikey::decode("did:key:z6Mk0"),
+        Err(invalid_symbol.clone().into())
+    );
+    let err = multikey::decode("did:key:z6Mk0").unwrap_err();
+    assert_eq!(err.to_string(), invalid_symbol.to_string());
+    let source = std::error::Error::source(&err).unwrap();
+    assert_eq!(source.to_string(), invalid_symbol.to_string());
+
+    let encode = |bytes: &[u8]| multibase::encode(Base::Base58Btc, bytes);
+    // Truncated, non-minimal and too long varints.
+    assert_eq!(multikey::decode(encode(&[0xed])), Err(Error::InvalidVarint));
+    assert_eq!(
+        multikey::decode(encode(&[0xed, 0x81, 0x00])),
+        Err(Error::InvalidVarint)
+    );
+    assert_eq!(
+        multikey::decode(encode(&[0xff; 10])),
+        Err(Error::InvalidVarint)
+    );
+    // The varint of 0x1200 is `80 24`, not `80 24 00`.
+    assert_eq!(
+        multikey::decode(encode(&[0x80, 0xa4, 0x00])),
+        Err(Error::InvalidVarint)
+    );
+    assert_eq!(
+        multikey::decode(encode(&[0x12, 0x20, 0x00])),
+        Err(Error::UnknownMulticodec(0x12))
+    );
+    assert_eq!(
+        multikey::decode(encode(&[0xed, 0x01, 0x00])),
+        Err(Error::InvalidKeyLength {
+            len: 1,
+            expected: 32,
+        })
+    );
+    assert_eq!(
+        multikey::encode(KeyType::Ed25519, [0; 33]),
+        Err(Error::InvalidKeyLength {
+            len: 33,
+            expected: 32,
+        })
+    );
+
+    assert_eq!(
+        Error::InvalidKeyLength {
+            len: 33,
+            expected: 32
+        }
+        .to_string(),
+        "Invalid key length: 33 bytes, expected 32"
+    );
+
+    assert_eq!(KeyType::from_codec(0x1200), Ok(KeyType::P256));
+    assert_eq!(KeyType::P256.to_string(), "p256-pub");
+}
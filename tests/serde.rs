@@ -0,0 +1,89 @@
+use multibase::Base;
+use serde::{Deserialize, Serialize};
+use serde_test::{assert_tokens, Compact, Configure, Token};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Key {
+    #[serde(with = "multibase::serde::base58btc")]
+    array: [u8; 5],
+    #[serde(with = "multibase::serde::base16")]
+    vec: Vec<u8>,
+}
+
+#[test]
+fn serde_bytes() {
+    let key = Key {
+        array: *b"hello",
+        vec: b"world".to_vec(),
+    };
+    let json = r#"{"array":"zCn8eVZg","vec":"f776f726c64"}"#;
+    assert_eq!(serde_json::to_string(&key).unwrap(), json);
+    assert_eq!(serde_json::from_str::<Key>(json).unwrap(), key);
+
+    // Wrong base, wrong length
+    assert!(serde_json::from_str::<Key>(r#"{"array":"f68656c6c6f","vec":"f"}"#).is_err());
+    assert!(serde_json::from_str::<Key>(r#"{"array":"zCn8eV","vec":"f"}"#).is_err());
+
+    let tokens = [
+        Token::Struct {
+            name: "Key",
+            len: 2,
+        },
+        Token::Str("array"),
+        Token::Bytes(b"hello"),
+        Token::Str("vec"),
+        Token::Bytes(b"world"),
+        Token::StructEnd,
+    ];
+    assert_tokens(&key.compact(), &tokens);
+}
+
+#[test]
+fn serde_any() {
+    #[derive(Deserialize)]
+    struct Any(#[serde(deserialize_with = "multibase::serde::any::deserialize")] Vec<u8>);
+
+    for input in ["zCn8eVZg", "f68656c6c6f", "maGVsbG8", "bnbswy3dp"] {
+        let json = serde_json::to_string(input).unwrap();
+        assert_eq!(serde_json::from_str::<Any>(&json).unwrap().0, b"hello");
+    }
+}
+
+#[test]
+fn serde_max() {
+    #[derive(Deserialize, Debug)]
+    struct Key(
+        #[serde(deserialize_with = "multibase::serde::base58btc::deserialize_max::<5, _, _>")]
+        Vec<u8>,
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Key>(r#""zCn8eVZg""#).unwrap().0,
+        b"hello"
+    );
+    // Over the limit, with and without decoding.
+    let err = serde_json::from_str::<Key>(r#""z7paNL19xttacUY""#).unwrap_err();
+    assert!(err.to_string().contains("too long"), "{}", err);
+    let err = serde_json::from_str::<Key>(r#""z1111111""#).unwrap_err();
+    assert!(err.to_string().contains("too long"), "{}", err);
+
+    let tokens = [
+        Token::NewtypeStruct { name: "Key" },
+        Token::Bytes(b"hello world"),
+    ];
+    serde_test::assert_de_tokens_error::<Compact<Key>>(
+        &tokens,
+        "Output may be too long: 11 bytes, the limit is 5",
+    );
+}
+
+#[test]
+fn serde_base() {
+    assert_tokens(&Base::Base58Btc.readable(), &[Token::Str("base58btc")]);
+    assert_tokens(&Base::Base36Lower.readable(), &[Token::Str("base36")]);
+    assert_eq!(
+        serde_json::from_str::<Base>(r#""base256emoji""#).unwrap(),
+        Base::Base256Emoji
+    );
+    assert!(serde_json::from_str::<Base>(r#""base42""#).is_err());
+}
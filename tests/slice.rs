@@ -0,0 +1,55 @@
+//! The slice and fixed-capacity functions, which work without `alloc`.
+
+use multibase::{
+    decode_to_array, decode_to_slice, encode_to_array, encode_to_slice, multibase, ArrayBytes,
+    Base, Error,
+};
+
+#[test]
+fn slice_round_trip() {
+    let input = b"\0\0hello world\xff";
+    for base in Base::iter() {
+        let mut encoded = [0; 256];
+        if !base.is_supported() {
+            assert_eq!(
+                encode_to_slice(base, input, &mut encoded),
+                Err(Error::Unsupported(base))
+            );
+            assert_eq!(base.encoded_len(input.len()), Err(Error::Unsupported(base)));
+            assert_eq!(base.decoded_len_max(4), Err(Error::Unsupported(base)));
+            continue;
+        }
+        if base == Base::Identity {
+            continue;
+        }
+        let len = encode_to_slice(base, input, &mut encoded).unwrap();
+        let encoded = core::str::from_utf8(&encoded[..len]).unwrap();
+
+        let mut decoded = [0; 32];
+        let (decoded_base, len) = decode_to_slice(encoded, &mut decoded).unwrap();
+        assert_eq!(decoded_base, base);
+        assert_eq!(&decoded[..len], input, "{:?}", base);
+    }
+}
+
+#[test]
+fn array_round_trip() {
+    let encoded = encode_to_array::<16, _>(Base::Base58Btc, b"hello").unwrap();
+    assert_eq!(encoded, "zCn8eVZg");
+
+    let (base, decoded): (_, ArrayBytes<5>) = decode_to_array(encoded).unwrap();
+    assert_eq!(base, Base::Base58Btc);
+    assert_eq!(decoded, *b"hello");
+    assert_eq!(decode_to_array::<4, _>(encoded), Err(Error::BufferTooSmall));
+
+    const HELLO: [u8; 5] = multibase!("zCn8eVZg");
+    assert_eq!(decoded, HELLO);
+}
+
+#[test]
+fn unknown_name() {
+    match Base::from_name("base42") {
+        Err(Error::UnknownName(name)) => assert_eq!(name, "base42"),
+        result => panic!("{:?}", result),
+    }
+}
@@ -0,0 +1,49 @@
+//! The bases of the disabled cargo features, which are recognized but not supported.
+
+use multibase::{decode, encode, transcode, Base, Error, Multibase};
+
+/// Get a valid string of `base`, or a string with its code if it is not supported.
+fn encoded(base: Base) -> String {
+    if base.is_supported() {
+        encode(base, b"hello")
+    } else {
+        format!("{}nbswy3dp", base.code())
+    }
+}
+
+#[test]
+fn decode_unsupported() {
+    for base in Base::iter().filter(|base| !base.is_supported()) {
+        let input = encoded(base);
+        assert_eq!(decode(&input), Err(Error::Unsupported(base)));
+        assert_eq!(
+            input.parse::<Multibase>().err(),
+            Some(Error::Unsupported(base))
+        );
+        assert_eq!(base.try_encode(b"hello"), Err(Error::Unsupported(base)));
+        assert_eq!(base.encoded_len(5), Err(Error::Unsupported(base)));
+    }
+}
+
+#[test]
+fn transcode_unsupported() {
+    for source in Base::iter() {
+        let input = encoded(source);
+        for target in Base::iter() {
+            let expected = if !source.is_supported() {
+                Err(Error::Unsupported(source))
+            } else if !target.is_supported() {
+                Err(Error::Unsupported(target))
+            } else {
+                Ok(encode(target, b"hello"))
+            };
+            assert_eq!(
+                transcode(&input, target),
+                expected,
+                "{:?} to {:?}",
+                source,
+                target
+            );
+        }
+    }
+}